@@ -0,0 +1,163 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{UiTransactionStatusMeta, UiTransactionTokenBalance};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::token_ledger::{routed_amount, TokenTransfer};
+use crate::SwapTransaction;
+
+/// Net balance change of every account over a whole transaction, read from
+/// its meta.
+pub struct BalanceChanges<'a> {
+    account_keys: &'a [Pubkey],
+    meta: &'a UiTransactionStatusMeta,
+    pre_token: HashMap<u8, u64>,
+    post_token: HashMap<u8, u64>,
+}
+
+impl<'a> BalanceChanges<'a> {
+    pub fn new(account_keys: &'a [Pubkey], meta: &'a UiTransactionStatusMeta) -> Self {
+        BalanceChanges {
+            account_keys,
+            meta,
+            pre_token: token_amounts(&meta.pre_token_balances),
+            post_token: token_amounts(&meta.post_token_balances),
+        }
+    }
+
+    /// Signed change of `account` in its smallest unit. Token accounts use
+    /// the token balances; anything else, such as a wallet paying native
+    /// SOL, uses lamports. The payer gets the transaction fee and the rent of
+    /// token accounts the transaction created added back, as neither is
+    /// swapped.
+    pub fn delta(&self, account: &Pubkey) -> Option<i128> {
+        let index = self.account_keys.iter().position(|key| key == account)?;
+        let token_index = u8::try_from(index).ok()?;
+
+        if self.pre_token.contains_key(&token_index) || self.post_token.contains_key(&token_index) {
+            let pre = self.pre_token.get(&token_index).copied().unwrap_or(0);
+            let post = self.post_token.get(&token_index).copied().unwrap_or(0);
+            return Some(post as i128 - pre as i128);
+        }
+
+        let pre = *self.meta.pre_balances.get(index)?;
+        let post = *self.meta.post_balances.get(index)?;
+        let paid = if index == 0 {
+            self.meta.fee as i128 + self.created_account_rent()
+        } else {
+            0
+        };
+        Some(post as i128 - pre as i128 + paid)
+    }
+
+    /// Lamports put into token accounts that only have a post balance, i.e.
+    /// were created by the transaction.
+    fn created_account_rent(&self) -> i128 {
+        self.post_token
+            .keys()
+            .filter(|index| !self.pre_token.contains_key(index))
+            .filter_map(|index| {
+                let index = *index as usize;
+                let pre = *self.meta.pre_balances.get(index)?;
+                let post = *self.meta.post_balances.get(index)?;
+                Some(post.saturating_sub(pre) as i128)
+            })
+            .sum()
+    }
+
+    /// Mint of a token account, as listed in the token balances.
+    pub fn mint(&self, account: &Pubkey) -> Option<Pubkey> {
+        let index = self.account_keys.iter().position(|key| key == account)?;
+        [
+            &self.meta.pre_token_balances,
+            &self.meta.post_token_balances,
+        ]
+        .into_iter()
+        .filter_map(|balances| match balances {
+            OptionSerializer::Some(balances) => Some(balances),
+            _ => None,
+        })
+        .flatten()
+        .find(|balance| balance.account_index as usize == index)
+        .and_then(|balance| Pubkey::from_str(&balance.mint).ok())
+    }
+
+    /// Amount that left `account`, if it decreased.
+    pub fn spent(&self, account: &Pubkey) -> Option<u64> {
+        self.delta(account)
+            .and_then(|delta| u64::try_from(-delta).ok())
+            .filter(|amount| *amount > 0)
+    }
+
+    /// Amount that arrived in `account`, if it increased.
+    pub fn received(&self, account: &Pubkey) -> Option<u64> {
+        self.delta(account)
+            .and_then(|delta| u64::try_from(delta).ok())
+            .filter(|amount| *amount > 0)
+    }
+
+    /// Fills `realized_in`/`realized_out` from the swap's source and
+    /// destination accounts. When several instructions of one transaction
+    /// touch the same account the net change of the whole transaction is
+    /// reported for each of them.
+    pub fn apply(&self, swap: &mut SwapTransaction) {
+        swap.realized_in = swap
+            .mint_token_account_in
+            .and_then(|account| self.spent(&account));
+        swap.realized_out = swap
+            .mint_token_account_out
+            .and_then(|account| self.received(&account));
+    }
+
+    /// Like [`apply`](Self::apply) for the swap at `path`, preferring what
+    /// the swap and its CPIs transferred out of its source and into its
+    /// destination account. A swap that moved neither, e.g. a pump.fun sell
+    /// paying out of the bonding curve's lamports, falls back to the net
+    /// change.
+    pub(crate) fn apply_transfers(
+        &self,
+        swap: &mut SwapTransaction,
+        path: &[usize],
+        transfers: &[TokenTransfer],
+    ) {
+        self.apply(swap);
+        if let Some(account) = swap.mint_token_account_in {
+            let routed = routed_amount(transfers, path, |transfer| transfer.source == account);
+            swap.realized_in = routed.or(swap.realized_in);
+        }
+        if let Some(account) = swap.mint_token_account_out {
+            let routed = routed_amount(transfers, path, |transfer| transfer.destination == account);
+            swap.realized_out = routed.or(swap.realized_out);
+        }
+    }
+
+    /// Fills whichever of the swap's mints the instruction does not name
+    /// from the token balances of its source and destination accounts.
+    pub fn fill_mints(&self, swap: &mut SwapTransaction) {
+        if swap.mint_token_in.is_none() {
+            swap.mint_token_in = swap
+                .mint_token_account_in
+                .and_then(|account| self.mint(&account));
+        }
+        if swap.mint_token_out.is_none() {
+            swap.mint_token_out = swap
+                .mint_token_account_out
+                .and_then(|account| self.mint(&account));
+        }
+    }
+}
+
+fn token_amounts(balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>) -> HashMap<u8, u64> {
+    let OptionSerializer::Some(balances) = balances else {
+        return HashMap::new();
+    };
+
+    balances
+        .iter()
+        .filter_map(|balance| {
+            let amount = balance.ui_token_amount.amount.parse().ok()?;
+            Some((balance.account_index, amount))
+        })
+        .collect()
+}
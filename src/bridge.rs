@@ -0,0 +1,144 @@
+use carbon_okx_dex_decoder::types::{AdaptorID, BridgeToArgs, SwapType};
+use serde::{Deserialize, Serialize};
+use solana_sdk::bs58;
+use solana_sdk::hash::hash;
+
+/// Outbound cross-chain transfer requested by an OKX `*_from_swap`
+/// instruction.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BridgeTransfer {
+    pub adaptor_id: AdaptorID,
+    /// Name of the bridge behind `adaptor_id`, when OKX has published it.
+    pub bridge: Option<String>,
+    pub to_chain_id: u64,
+    pub to_chain: Option<String>,
+    /// Recipient rendered in the destination chain's address format, or as
+    /// hex when the chain or the payload is not recognised.
+    pub to: String,
+    pub amount: u64,
+    pub order_id: u64,
+    pub swap_type: SwapType,
+    pub data: Vec<u8>,
+    pub ext_data: Vec<u8>,
+}
+
+impl From<&BridgeToArgs> for BridgeTransfer {
+    fn from(args: &BridgeToArgs) -> Self {
+        let chain = chain(args.to_chain_id);
+        BridgeTransfer {
+            adaptor_id: args.adaptor_id.clone(),
+            bridge: bridge_name(&args.adaptor_id).map(str::to_string),
+            to_chain_id: args.to_chain_id,
+            to_chain: chain.map(|(name, _)| name.to_string()),
+            to: render_address(&args.to, chain.map(|(_, family)| family)),
+            amount: args.amount,
+            order_id: args.order_id,
+            swap_type: args.swap_type.clone(),
+            data: args.data.clone(),
+            ext_data: args.ext_data.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AddressFamily {
+    Evm,
+    Solana,
+    Tron,
+    Other,
+}
+
+fn bridge_name(adaptor_id: &AdaptorID) -> Option<&'static str> {
+    match adaptor_id {
+        AdaptorID::Cctp => Some("CCTP"),
+        AdaptorID::Wormhole => Some("Wormhole"),
+        AdaptorID::Meson => Some("Meson"),
+        AdaptorID::Debridgedln => Some("deBridge DLN"),
+        _ => None,
+    }
+}
+
+/// Chains by OKX chain index, which matches the EIP-155 chain ID for EVM
+/// chains.
+fn chain(chain_id: u64) -> Option<(&'static str, AddressFamily)> {
+    let chain = match chain_id {
+        1 => ("Ethereum", AddressFamily::Evm),
+        10 => ("Optimism", AddressFamily::Evm),
+        25 => ("Cronos", AddressFamily::Evm),
+        56 => ("BNB Chain", AddressFamily::Evm),
+        100 => ("Gnosis", AddressFamily::Evm),
+        137 => ("Polygon", AddressFamily::Evm),
+        169 => ("Manta Pacific", AddressFamily::Evm),
+        196 => ("X Layer", AddressFamily::Evm),
+        250 => ("Fantom", AddressFamily::Evm),
+        324 => ("zkSync Era", AddressFamily::Evm),
+        1101 => ("Polygon zkEVM", AddressFamily::Evm),
+        5000 => ("Mantle", AddressFamily::Evm),
+        8453 => ("Base", AddressFamily::Evm),
+        42161 => ("Arbitrum", AddressFamily::Evm),
+        43114 => ("Avalanche C-Chain", AddressFamily::Evm),
+        59144 => ("Linea", AddressFamily::Evm),
+        81457 => ("Blast", AddressFamily::Evm),
+        534352 => ("Scroll", AddressFamily::Evm),
+        195 => ("Tron", AddressFamily::Tron),
+        501 => ("Solana", AddressFamily::Solana),
+        0 => ("Bitcoin", AddressFamily::Other),
+        607 => ("TON", AddressFamily::Other),
+        637 => ("Aptos", AddressFamily::Other),
+        784 => ("Sui", AddressFamily::Other),
+        _ => return None,
+    };
+    Some(chain)
+}
+
+fn render_address(to: &[u8], family: Option<AddressFamily>) -> String {
+    // Some adaptors pass the recipient as the address string itself, which
+    // besides alphanumerics may hold the base64url and padding characters
+    // of TON addresses or the `:` of raw workchain addresses.
+    if let Ok(text) = std::str::from_utf8(to) {
+        if !text.is_empty()
+            && text
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '=' | ':'))
+        {
+            return text.to_string();
+        }
+    }
+
+    match family {
+        Some(AddressFamily::Evm) => match evm_address(to) {
+            Some(address) => format!("0x{}", hex(address)),
+            None => hex(to),
+        },
+        Some(AddressFamily::Tron) => match evm_address(to) {
+            Some(address) => tron_address(address),
+            None => hex(to),
+        },
+        Some(AddressFamily::Solana) if to.len() == 32 => bs58::encode(to).into_string(),
+        _ => hex(to),
+    }
+}
+
+/// The 20-byte account of an EVM-style address, also accepting it
+/// left-padded to a 32-byte word.
+fn evm_address(to: &[u8]) -> Option<&[u8]> {
+    match to.len() {
+        20 => Some(to),
+        32 if to[..12].iter().all(|byte| *byte == 0) => Some(&to[12..]),
+        _ => None,
+    }
+}
+
+/// Base58Check with Tron's 0x41 mainnet prefix.
+fn tron_address(address: &[u8]) -> String {
+    let mut payload = Vec::with_capacity(25);
+    payload.push(0x41);
+    payload.extend_from_slice(address);
+    let checksum = hash(hash(&payload).as_ref());
+    payload.extend_from_slice(&checksum.as_ref()[..4]);
+    bs58::encode(payload).into_string()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::balances::BalanceChanges;
+use crate::SwapTransaction;
+
+/// OKX commission rates are expressed over this denominator, i.e. in basis
+/// points.
+pub const OKX_COMMISSION_DENOMINATOR: u64 = 10_000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeSide {
+    /// Charged in the input token, on top of the amount swapped.
+    Input,
+    /// Charged in the output token, before it reaches the user.
+    Output,
+}
+
+/// Integrator commission taken by an aggregator instruction.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Commission {
+    /// Wallet or token account the commission is paid to.
+    pub recipient: Pubkey,
+    /// Token the commission is charged in; wrapped SOL for native payouts.
+    pub mint: Pubkey,
+    pub bps: u16,
+    pub side: FeeSide,
+    /// Commission in `mint`'s smallest unit. Output-side commissions depend
+    /// on the executed output and are only filled by the transaction-level
+    /// decoder.
+    pub amount: Option<u64>,
+}
+
+/// Commission owed on `net`, the amount actually swapped or received, when
+/// the commission itself is carved out of the gross amount:
+/// `net * bps / (denominator - bps)`.
+pub(crate) fn okx_commission_on_net(net: u64, bps: u16) -> Option<u64> {
+    let remaining = OKX_COMMISSION_DENOMINATOR.checked_sub(bps as u64)?;
+    let amount = (net as u128 * bps as u128).checked_div(remaining as u128)?;
+    u64::try_from(amount).ok()
+}
+
+/// Commission taken out of `gross`: `gross * bps / denominator`.
+pub(crate) fn okx_commission_on_gross(gross: u64, bps: u16) -> u64 {
+    (gross as u128 * bps as u128 / OKX_COMMISSION_DENOMINATOR as u128) as u64
+}
+
+/// Fills the amount of an output-side commission once the swap's output is
+/// known. Executed totals from swap events are the gross output of the
+/// route; otherwise the balance change is what reached the user, net of the
+/// commission.
+pub(crate) fn settle_output_commission(swap: &mut SwapTransaction) {
+    let Some(commission) = &swap.commission else {
+        return;
+    };
+    if commission.side != FeeSide::Output || commission.amount.is_some() {
+        return;
+    }
+
+    let bps = commission.bps;
+    let amount = match carbon_okx_dex_decoder::legs::executed_totals(&swap.okx_legs) {
+        Some((_, gross_out)) => Some(okx_commission_on_gross(gross_out, bps)),
+        None => swap
+            .realized_out
+            .and_then(|net_out| okx_commission_on_net(net_out, bps)),
+    };
+
+    if let Some(commission) = &mut swap.commission {
+        commission.amount = amount;
+    }
+}
+
+/// Fills the amount of a Jupiter platform fee from what reached the fee
+/// account over the whole transaction. Only a fallback for transactions
+/// without inner instructions, whose fee events are gone.
+pub(crate) fn settle_platform_fee(swap: &mut SwapTransaction, balances: &BalanceChanges) {
+    if let Some(commission) = &mut swap.commission {
+        if commission.amount.is_none() {
+            commission.amount = balances.received(&commission.recipient);
+        }
+    }
+}
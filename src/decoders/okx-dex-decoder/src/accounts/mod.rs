@@ -1,5 +1,4 @@
 use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::CarbonDeserialize;
 
 use super::OkxDexDecoder;
 
@@ -0,0 +1,45 @@
+use carbon_core::borsh::BorshSerialize;
+use carbon_core::deserialize::{ArrangeAccounts, CarbonDeserialize};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::PROGRAM_ID;
+
+/// Encodes an instruction back into the form the program expects: its
+/// discriminator followed by the borsh-serialized arguments, and the named
+/// accounts followed by the DEX accounts the route needs.
+pub trait BuildInstruction: CarbonDeserialize + BorshSerialize + ArrangeAccounts {
+    /// Named accounts in program order. Accounts the program writes to, or
+    /// may write to, are marked writable; the payer is the only signer.
+    fn account_metas(accounts: &Self::ArrangedAccounts) -> Vec<AccountMeta>;
+
+    fn instruction(
+        &self,
+        accounts: &Self::ArrangedAccounts,
+        remaining_accounts: &[AccountMeta],
+    ) -> Instruction {
+        self.instruction_for_program(PROGRAM_ID, accounts, remaining_accounts)
+    }
+
+    /// Like [`BuildInstruction::instruction`], targeting another router
+    /// deployment.
+    fn instruction_for_program(
+        &self,
+        program_id: Pubkey,
+        accounts: &Self::ArrangedAccounts,
+        remaining_accounts: &[AccountMeta],
+    ) -> Instruction {
+        let mut data = Self::DISCRIMINATOR.to_vec();
+        self.serialize(&mut data)
+            .expect("serializing into a Vec cannot fail");
+
+        let mut account_metas = Self::account_metas(accounts);
+        account_metas.extend_from_slice(remaining_accounts);
+
+        Instruction {
+            program_id,
+            accounts: account_metas,
+            data,
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use solana_sdk::{pubkey, pubkey::Pubkey};
+
+use crate::legs::SwapLeg;
+use crate::programs::OkxPrograms;
+use crate::types::Dex;
+
+const SPL_TOKEN_SWAP: Pubkey = pubkey!("SwapsVeCiPHMUAtzQWZw7RjsKjgCjhwU55QGu4U1Szw");
+const SABER_STABLE_SWAP: Pubkey = pubkey!("SSwpkEEcbUqx4vtoEByFjSkhKdCT862DNVb52nZg1UZ");
+const ORCA_WHIRLPOOL: Pubkey = pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+const METEORA_DYNAMIC_POOL: Pubkey = pubkey!("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB");
+const RAYDIUM_AMM_V4: Pubkey = pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
+const RAYDIUM_STABLE_SWAP: Pubkey = pubkey!("5quBtoiQqxF9Jv6KYKctB59NT3gtJD2Y65kdnB1Uev3h");
+const RAYDIUM_CLMM: Pubkey = pubkey!("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK");
+const ALDRIN_V1: Pubkey = pubkey!("AMM55ShdkoGRB5jVYPjWziwk8m5MpwyDgsMWHaMSQWH6");
+const ALDRIN_V2: Pubkey = pubkey!("CURVGoZn8zycx6FXwwevgBTB2gVvdbGTEpvMJDbgs2t4");
+const LIFINITY_V1: Pubkey = pubkey!("EewxydAPCCVuNEyrVN68PuSYdQ7wKn27V9Gjeoi8dy3S");
+const LIFINITY_V2: Pubkey = pubkey!("2wT8Yq49kHgDzXuPxZSaeLaH1qbmGXtEyPy64bL7aD3c");
+const FLUXBEAM: Pubkey = pubkey!("FLUXubRmkEi2q6K3Y9kBPg9248ggaZVsoSFhtJHSrm1X");
+const METEORA_DLMM: Pubkey = pubkey!("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo");
+const RAYDIUM_CPMM: Pubkey = pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+const OPENBOOK_V2: Pubkey = pubkey!("opnb2LAfJYbRMAHHvqjCwQxanZn7ReEHp1k81EohpZb");
+const PHOENIX: Pubkey = pubkey!("PhoeNiXZ8ByJGLkxNfZRnkUfjvmuYqLR89jjFHGqdXY");
+const OBRIC_V2: Pubkey = pubkey!("obriQD1zbpyLz95G5n7nJe6a4DPjpFwa5XYPoNm113y");
+const SANCTUM_INFINITY: Pubkey = pubkey!("5ocnV1qiCgaQR8Jb8xWnVbApfaygJ8tNoZfgPwsgx9kx");
+const SANCTUM_ROUTER: Pubkey = pubkey!("stkitrT1Uoy18Dk1fTrgPw8W1WfJm2oHkwHamvuEzaK");
+
+/// Programs a route may invoke without declaring them as a DEX.
+const SUPPORT_PROGRAMS: &[Pubkey] = &[
+    pubkey!("11111111111111111111111111111111"),
+    pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"),
+    pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"),
+    pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"),
+    pubkey!("ComputeBudget111111111111111111111111111111"),
+    pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"),
+    pubkey!("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo"),
+];
+
+/// Programs a `Dex` executes on. Empty for tags this crate does not know.
+pub fn dex_program_ids(dex: &Dex) -> &'static [Pubkey] {
+    match dex {
+        Dex::SplTokenSwap => &[SPL_TOKEN_SWAP],
+        Dex::StableSwap => &[SABER_STABLE_SWAP],
+        Dex::Whirlpool | Dex::WhirlpoolV2 => &[ORCA_WHIRLPOOL],
+        Dex::MeteoraDynamicpool => &[METEORA_DYNAMIC_POOL],
+        Dex::RaydiumSwap => &[RAYDIUM_AMM_V4],
+        Dex::RaydiumStableSwap => &[RAYDIUM_STABLE_SWAP],
+        Dex::RaydiumClmmSwap | Dex::RaydiumClmmSwapV2 => &[RAYDIUM_CLMM],
+        Dex::AldrinExchangeV1 => &[ALDRIN_V1],
+        Dex::AldrinExchangeV2 => &[ALDRIN_V2],
+        Dex::LifinityV1 => &[LIFINITY_V1],
+        Dex::LifinityV2 => &[LIFINITY_V2],
+        Dex::FluxBeam => &[FLUXBEAM],
+        Dex::MeteoraDlmm => &[METEORA_DLMM],
+        Dex::RaydiumCpmmSwap => &[RAYDIUM_CPMM],
+        Dex::OpenBookV2 => &[OPENBOOK_V2],
+        Dex::Phoenix => &[PHOENIX],
+        Dex::ObricV2 => &[OBRIC_V2],
+        Dex::SanctumAddLiq
+        | Dex::SanctumRemoveLiq
+        | Dex::SanctumNonWsolSwap
+        | Dex::SanctumWsolSwap => &[SANCTUM_INFINITY, SANCTUM_ROUTER],
+        Dex::Unknown(_) => &[],
+    }
+}
+
+/// Disagreement between the route an OKX swap declares and the programs it
+/// actually invoked.
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
+pub enum RouteMismatch {
+    /// The route names this DEX but none of its programs was invoked.
+    DexNotInvoked { dex: Dex },
+    /// A program was invoked that no DEX of the route runs on.
+    UndeclaredProgram { program_id: Pubkey },
+}
+
+/// Compares the declared `legs` with `invoked`, the programs the swap
+/// instruction called directly; calls into `okx_programs` are the router's
+/// own events. Routes through DEXes without a known program cannot be
+/// attributed, so undeclared programs are only reported when every leg is
+/// known.
+pub fn check_invoked_programs(
+    legs: &[SwapLeg],
+    invoked: &[Pubkey],
+    okx_programs: &OkxPrograms,
+) -> Vec<RouteMismatch> {
+    let mut mismatches = Vec::new();
+
+    let mut declared_dexes: Vec<&Dex> = Vec::new();
+    for leg in legs {
+        if !declared_dexes.contains(&&leg.dex) {
+            declared_dexes.push(&leg.dex);
+        }
+    }
+
+    for dex in &declared_dexes {
+        let programs = dex_program_ids(dex);
+        if !programs.is_empty() && !invoked.iter().any(|program| programs.contains(program)) {
+            mismatches.push(RouteMismatch::DexNotInvoked {
+                dex: (*dex).clone(),
+            });
+        }
+    }
+
+    if declared_dexes
+        .iter()
+        .any(|dex| dex_program_ids(dex).is_empty())
+    {
+        return mismatches;
+    }
+
+    let mut reported = Vec::new();
+    for program_id in invoked {
+        let declared = declared_dexes
+            .iter()
+            .any(|dex| dex_program_ids(dex).contains(program_id));
+        if declared
+            || SUPPORT_PROGRAMS.contains(program_id)
+            || okx_programs.contains(program_id)
+            || reported.contains(program_id)
+        {
+            continue;
+        }
+        reported.push(*program_id);
+        mismatches.push(RouteMismatch::UndeclaredProgram {
+            program_id: *program_id,
+        });
+    }
+
+    mismatches
+}
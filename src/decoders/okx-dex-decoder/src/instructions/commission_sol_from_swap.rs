@@ -4,7 +4,7 @@ use super::super::types::*;
 use carbon_core::{CarbonDeserialize, borsh};
 
 
-#[derive(CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
+#[derive(CarbonDeserialize, borsh::BorshSerialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
 #[carbon(discriminator = "0x813b450a844c2314")]
 pub struct CommissionSolFromSwap{
     pub args: SwapArgs,
@@ -14,6 +14,7 @@ pub struct CommissionSolFromSwap{
     pub len: u8,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct CommissionSolFromSwapInstructionAccounts {
     pub payer: solana_sdk::pubkey::Pubkey,
     pub source_token_account: solana_sdk::pubkey::Pubkey,
@@ -64,4 +65,22 @@ impl carbon_core::deserialize::ArrangeAccounts for CommissionSolFromSwap {
             commission_account: commission_account.pubkey,
         })
     }
-}
\ No newline at end of file
+}
+
+impl crate::builder::BuildInstruction for CommissionSolFromSwap {
+    fn account_metas(accounts: &Self::ArrangedAccounts) -> Vec<solana_sdk::instruction::AccountMeta> {
+        vec![
+            solana_sdk::instruction::AccountMeta::new(accounts.payer, true),
+            solana_sdk::instruction::AccountMeta::new(accounts.source_token_account, false),
+            solana_sdk::instruction::AccountMeta::new(accounts.destination_token_account, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.source_mint, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.destination_mint, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.bridge_program, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.associated_token_program, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.token_program, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.token_2022_program, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.system_program, false),
+            solana_sdk::instruction::AccountMeta::new(accounts.commission_account, false),
+        ]
+    }
+}
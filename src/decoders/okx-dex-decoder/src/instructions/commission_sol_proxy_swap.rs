@@ -4,7 +4,7 @@ use super::super::types::*;
 use carbon_core::{CarbonDeserialize, borsh};
 
 
-#[derive(CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
+#[derive(CarbonDeserialize, borsh::BorshSerialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
 #[carbon(discriminator = "0x1e21d05b1f9d2512")]
 pub struct CommissionSolProxySwap{
     pub data: SwapArgs,
@@ -13,6 +13,7 @@ pub struct CommissionSolProxySwap{
     pub order_id: u64,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct CommissionSolProxySwapInstructionAccounts {
     pub payer: solana_sdk::pubkey::Pubkey,
     pub source_token_account: solana_sdk::pubkey::Pubkey,
@@ -69,4 +70,24 @@ impl carbon_core::deserialize::ArrangeAccounts for CommissionSolProxySwap {
             system_program: system_program.pubkey,
         })
     }
-}
\ No newline at end of file
+}
+
+impl crate::builder::BuildInstruction for CommissionSolProxySwap {
+    fn account_metas(accounts: &Self::ArrangedAccounts) -> Vec<solana_sdk::instruction::AccountMeta> {
+        vec![
+            solana_sdk::instruction::AccountMeta::new(accounts.payer, true),
+            solana_sdk::instruction::AccountMeta::new(accounts.source_token_account, false),
+            solana_sdk::instruction::AccountMeta::new(accounts.destination_token_account, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.source_mint, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.destination_mint, false),
+            solana_sdk::instruction::AccountMeta::new(accounts.commission_account, false),
+            solana_sdk::instruction::AccountMeta::new(accounts.sa_authority, false),
+            solana_sdk::instruction::AccountMeta::new(accounts.source_token_sa, false),
+            solana_sdk::instruction::AccountMeta::new(accounts.destination_token_sa, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.source_token_program, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.destination_token_program, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.associated_token_program, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.system_program, false),
+        ]
+    }
+}
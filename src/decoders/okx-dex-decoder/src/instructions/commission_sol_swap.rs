@@ -4,12 +4,13 @@ use super::super::types::*;
 use carbon_core::{CarbonDeserialize, borsh};
 
 
-#[derive(CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
+#[derive(CarbonDeserialize, borsh::BorshSerialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
 #[carbon(discriminator = "0x5180864972492d5e")]
 pub struct CommissionSolSwap{
     pub data: CommissionSwapArgs,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct CommissionSolSwapInstructionAccounts {
     pub payer: solana_sdk::pubkey::Pubkey,
     pub source_token_account: solana_sdk::pubkey::Pubkey,
@@ -48,4 +49,18 @@ impl carbon_core::deserialize::ArrangeAccounts for CommissionSolSwap {
             system_program: system_program.pubkey,
         })
     }
-}
\ No newline at end of file
+}
+
+impl crate::builder::BuildInstruction for CommissionSolSwap {
+    fn account_metas(accounts: &Self::ArrangedAccounts) -> Vec<solana_sdk::instruction::AccountMeta> {
+        vec![
+            solana_sdk::instruction::AccountMeta::new(accounts.payer, true),
+            solana_sdk::instruction::AccountMeta::new(accounts.source_token_account, false),
+            solana_sdk::instruction::AccountMeta::new(accounts.destination_token_account, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.source_mint, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.destination_mint, false),
+            solana_sdk::instruction::AccountMeta::new(accounts.commission_account, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.system_program, false),
+        ]
+    }
+}
@@ -4,13 +4,14 @@ use super::super::types::*;
 use carbon_core::{CarbonDeserialize, borsh};
 
 
-#[derive(CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
+#[derive(CarbonDeserialize, borsh::BorshSerialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
 #[carbon(discriminator = "0x71841f4a63a93992")]
 pub struct CommissionSolSwap2{
     pub data: CommissionSwapArgs,
     pub order_id: u64,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct CommissionSolSwap2InstructionAccounts {
     pub payer: solana_sdk::pubkey::Pubkey,
     pub source_token_account: solana_sdk::pubkey::Pubkey,
@@ -49,4 +50,18 @@ impl carbon_core::deserialize::ArrangeAccounts for CommissionSolSwap2 {
             system_program: system_program.pubkey,
         })
     }
-}
\ No newline at end of file
+}
+
+impl crate::builder::BuildInstruction for CommissionSolSwap2 {
+    fn account_metas(accounts: &Self::ArrangedAccounts) -> Vec<solana_sdk::instruction::AccountMeta> {
+        vec![
+            solana_sdk::instruction::AccountMeta::new(accounts.payer, true),
+            solana_sdk::instruction::AccountMeta::new(accounts.source_token_account, false),
+            solana_sdk::instruction::AccountMeta::new(accounts.destination_token_account, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.source_mint, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.destination_mint, false),
+            solana_sdk::instruction::AccountMeta::new(accounts.commission_account, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.system_program, false),
+        ]
+    }
+}
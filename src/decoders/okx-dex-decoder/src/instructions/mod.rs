@@ -1,5 +1,3 @@
-use crate::PROGRAM_ID;
-
 use super::OkxDexDecoder;
 pub mod commission_sol_from_swap;
 pub mod commission_sol_proxy_swap;
@@ -48,7 +46,7 @@ impl<'a> carbon_core::instruction::InstructionDecoder<'a> for OkxDexDecoder {
         &self,
         instruction: &solana_sdk::instruction::Instruction,
     ) -> Option<carbon_core::instruction::DecodedInstruction<Self::InstructionType>> {
-        if !instruction.program_id.eq(&PROGRAM_ID) {
+        if !self.programs.contains(&instruction.program_id) {
             return None;
         }
 
@@ -4,13 +4,14 @@ use super::super::types::*;
 use carbon_core::{CarbonDeserialize, borsh};
 
 
-#[derive(CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
+#[derive(CarbonDeserialize, borsh::BorshSerialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
 #[carbon(discriminator = "0x132c829448382cee")]
 pub struct ProxySwap{
     pub data: SwapArgs,
     pub order_id: u64,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct ProxySwapInstructionAccounts {
     pub payer: solana_sdk::pubkey::Pubkey,
     pub source_token_account: solana_sdk::pubkey::Pubkey,
@@ -64,4 +65,23 @@ impl carbon_core::deserialize::ArrangeAccounts for ProxySwap {
             system_program: system_program.pubkey,
         })
     }
-}
\ No newline at end of file
+}
+
+impl crate::builder::BuildInstruction for ProxySwap {
+    fn account_metas(accounts: &Self::ArrangedAccounts) -> Vec<solana_sdk::instruction::AccountMeta> {
+        vec![
+            solana_sdk::instruction::AccountMeta::new(accounts.payer, true),
+            solana_sdk::instruction::AccountMeta::new(accounts.source_token_account, false),
+            solana_sdk::instruction::AccountMeta::new(accounts.destination_token_account, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.source_mint, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.destination_mint, false),
+            solana_sdk::instruction::AccountMeta::new(accounts.sa_authority, false),
+            solana_sdk::instruction::AccountMeta::new(accounts.source_token_sa, false),
+            solana_sdk::instruction::AccountMeta::new(accounts.destination_token_sa, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.source_token_program, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.destination_token_program, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.associated_token_program, false),
+            solana_sdk::instruction::AccountMeta::new_readonly(accounts.system_program, false),
+        ]
+    }
+}
@@ -0,0 +1,140 @@
+use crate::instructions::OkxDexInstruction;
+use crate::types::{CommissionSwapArgs, Dex, Route, SwapArgs, SwapEvent};
+
+/// One DEX a swap routes through. `amounts[route]` of the input follows
+/// `routes[route]`, a path of hops; each hop splits its input between DEXes
+/// by percentage weight.
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
+pub struct SwapLeg {
+    pub route: usize,
+    pub hop: usize,
+    pub dex: Dex,
+    /// Percentage of the hop's input sent to this DEX.
+    pub weight: u8,
+    /// Share of the whole swap input flowing through this leg, in basis
+    /// points.
+    pub share_bps: u64,
+    /// Input of the leg. Only known up front for the first hop of a route;
+    /// later hops consume whatever the previous hop produced. Replaced by the
+    /// executed amount once a swap event is matched.
+    pub amount_in: Option<u64>,
+    /// Executed output, from the matching swap event.
+    pub amount_out: Option<u64>,
+}
+
+/// Expands `routes` into legs ordered by route, hop and DEX.
+pub fn expand_routes(amounts: &[u64], routes: &[Vec<Route>]) -> Vec<SwapLeg> {
+    let total: u128 = amounts.iter().map(|amount| *amount as u128).sum();
+    let mut legs = Vec::new();
+
+    for (route_index, (route_amount, hops)) in amounts.iter().zip(routes).enumerate() {
+        for (hop_index, hop) in hops.iter().enumerate() {
+            for (dex, weight) in hop.dexes.iter().zip(&hop.weights) {
+                let share_bps = (*route_amount as u128 * *weight as u128 * 100)
+                    .checked_div(total)
+                    .unwrap_or(0) as u64;
+                let amount_in = (hop_index == 0)
+                    .then(|| (*route_amount as u128 * *weight as u128 / 100) as u64);
+
+                legs.push(SwapLeg {
+                    route: route_index,
+                    hop: hop_index,
+                    dex: dex.clone(),
+                    weight: *weight,
+                    share_bps,
+                    amount_in,
+                    amount_out: None,
+                });
+            }
+        }
+    }
+
+    legs
+}
+
+/// Fills the executed amounts of `legs` from the swap events the program
+/// emits, which come in leg order. Each event goes to the next unfilled leg
+/// on the same DEX; returns how many events were matched.
+pub fn apply_events(legs: &mut [SwapLeg], events: &[SwapEvent]) -> usize {
+    let mut cursor = 0;
+    let mut matched = 0;
+
+    for event in events {
+        let Some(offset) = legs[cursor..]
+            .iter()
+            .position(|leg| leg.dex == event.dex && leg.amount_out.is_none())
+        else {
+            continue;
+        };
+
+        let leg = &mut legs[cursor + offset];
+        leg.amount_in = Some(event.amount_in);
+        leg.amount_out = Some(event.amount_out);
+        cursor += offset + 1;
+        matched += 1;
+    }
+
+    matched
+}
+
+/// Total input and output of a fully executed route: the inputs of every
+/// first hop and the outputs of every last hop. `None` until each leg has
+/// been matched to an event.
+pub fn executed_totals(legs: &[SwapLeg]) -> Option<(u64, u64)> {
+    if legs.is_empty() || legs.iter().any(|leg| leg.amount_out.is_none()) {
+        return None;
+    }
+
+    let mut amount_in = 0u64;
+    let mut amount_out = 0u64;
+    for leg in legs {
+        let last_hop = legs
+            .iter()
+            .filter(|other| other.route == leg.route)
+            .map(|other| other.hop)
+            .max();
+        if leg.hop == 0 {
+            amount_in = amount_in.checked_add(leg.amount_in?)?;
+        }
+        if Some(leg.hop) == last_hop {
+            amount_out = amount_out.checked_add(leg.amount_out?)?;
+        }
+    }
+
+    Some((amount_in, amount_out))
+}
+
+impl SwapArgs {
+    pub fn legs(&self) -> Vec<SwapLeg> {
+        expand_routes(&self.amounts, &self.routes)
+    }
+}
+
+impl CommissionSwapArgs {
+    pub fn legs(&self) -> Vec<SwapLeg> {
+        expand_routes(&self.amounts, &self.routes)
+    }
+}
+
+impl OkxDexInstruction {
+    /// Legs of any swap instruction, or `None` for instructions that carry no
+    /// route.
+    pub fn legs(&self) -> Option<Vec<SwapLeg>> {
+        let legs = match self {
+            OkxDexInstruction::CommissionSolFromSwap(ix) => ix.args.legs(),
+            OkxDexInstruction::CommissionSolProxySwap(ix) => ix.data.legs(),
+            OkxDexInstruction::CommissionSolSwap(ix) => ix.data.legs(),
+            OkxDexInstruction::CommissionSolSwap2(ix) => ix.data.legs(),
+            OkxDexInstruction::CommissionSplFromSwap(ix) => ix.args.legs(),
+            OkxDexInstruction::CommissionSplProxySwap(ix) => ix.data.legs(),
+            OkxDexInstruction::CommissionSplSwap(ix) => ix.data.legs(),
+            OkxDexInstruction::CommissionSplSwap2(ix) => ix.data.legs(),
+            OkxDexInstruction::FromSwapLog(ix) => ix.args.legs(),
+            OkxDexInstruction::ProxySwap(ix) => ix.data.legs(),
+            OkxDexInstruction::Swap(ix) => ix.data.legs(),
+            OkxDexInstruction::Swap2(ix) => ix.data.legs(),
+            OkxDexInstruction::SwapEvent(_) => return None,
+        };
+        Some(legs)
+    }
+}
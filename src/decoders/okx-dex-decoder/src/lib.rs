@@ -1,8 +1,18 @@
 use solana_sdk::{pubkey, pubkey::Pubkey};
 
-pub struct OkxDexDecoder;
+/// Decodes instructions of the router deployments in `programs`.
+#[derive(Debug, Default, Clone)]
+pub struct OkxDexDecoder {
+    pub programs: programs::OkxPrograms,
+}
 pub mod accounts;
+pub mod builder;
+pub mod dex_programs;
 pub mod instructions;
+pub mod legs;
+pub mod programs;
 pub mod types;
+pub mod unknown_tags;
+pub mod validation;
 
-pub(crate) const PROGRAM_ID: Pubkey = pubkey!("6m2CDdhRgxpH4WjvdzxAYbGxwdGUz5MziiL5jek2kBma");
\ No newline at end of file
+pub const PROGRAM_ID: Pubkey = pubkey!("6m2CDdhRgxpH4WjvdzxAYbGxwdGUz5MziiL5jek2kBma");
@@ -0,0 +1,68 @@
+use solana_sdk::pubkey::Pubkey;
+
+use crate::PROGRAM_ID;
+
+/// A deployment of the OKX router, optionally tagged with the router version
+/// it runs.
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
+pub struct OkxProgram {
+    pub program_id: Pubkey,
+    pub version: Option<String>,
+}
+
+/// Program IDs a decoder accepts as the OKX router. The default set is just
+/// [`PROGRAM_ID`].
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
+pub struct OkxPrograms {
+    programs: Vec<OkxProgram>,
+}
+
+impl Default for OkxPrograms {
+    fn default() -> Self {
+        OkxPrograms::new(vec![OkxProgram {
+            program_id: PROGRAM_ID,
+            version: None,
+        }])
+    }
+}
+
+impl OkxPrograms {
+    /// A set of exactly `programs`, e.g. devnet deployments only.
+    pub fn new(programs: Vec<OkxProgram>) -> Self {
+        OkxPrograms { programs }
+    }
+
+    pub fn programs(&self) -> &[OkxProgram] {
+        &self.programs
+    }
+
+    /// Accepts `program_id` as an OKX router, or updates its version tag if
+    /// it is already accepted.
+    pub fn register(&mut self, program_id: Pubkey, version: Option<&str>) {
+        let version = version.map(str::to_string);
+        match self
+            .programs
+            .iter_mut()
+            .find(|program| program.program_id == program_id)
+        {
+            Some(program) => program.version = version,
+            None => self.programs.push(OkxProgram {
+                program_id,
+                version,
+            }),
+        }
+    }
+
+    pub fn contains(&self, program_id: &Pubkey) -> bool {
+        self.programs
+            .iter()
+            .any(|program| program.program_id == *program_id)
+    }
+
+    pub fn version(&self, program_id: &Pubkey) -> Option<&str> {
+        self.programs
+            .iter()
+            .find(|program| program.program_id == *program_id)
+            .and_then(|program| program.version.as_deref())
+    }
+}
@@ -1,9 +1,9 @@
+use carbon_core::borsh;
 
+use crate::unknown_tags::{record, UnknownTagKind};
 
-use carbon_core::{CarbonDeserialize, borsh};
-
-
-#[derive(CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
+/// Decoded by hand like [`Dex`](crate::types::Dex).
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
 pub enum AdaptorID {
     Bridge0,
     Bridge1,
@@ -40,6 +40,106 @@ pub enum AdaptorID {
     Bridge32,
     Bridge33,
     Debridgedln,
+    Unknown(u8),
 }
 
+impl AdaptorID {
+    pub fn from_tag(tag: u8) -> Self {
+        match tag {
+            0 => AdaptorID::Bridge0,
+            1 => AdaptorID::Bridge1,
+            2 => AdaptorID::Bridge2,
+            3 => AdaptorID::Bridge3,
+            4 => AdaptorID::Bridge4,
+            5 => AdaptorID::Bridge5,
+            6 => AdaptorID::Bridge6,
+            7 => AdaptorID::Bridge7,
+            8 => AdaptorID::Bridge8,
+            9 => AdaptorID::Bridge9,
+            10 => AdaptorID::Bridge10,
+            11 => AdaptorID::Bridge11,
+            12 => AdaptorID::Bridge12,
+            13 => AdaptorID::Bridge13,
+            14 => AdaptorID::Bridge14,
+            15 => AdaptorID::Bridge15,
+            16 => AdaptorID::Bridge16,
+            17 => AdaptorID::Bridge17,
+            18 => AdaptorID::Cctp,
+            19 => AdaptorID::Bridge19,
+            20 => AdaptorID::Bridge20,
+            21 => AdaptorID::Wormhole,
+            22 => AdaptorID::Meson,
+            23 => AdaptorID::Bridge23,
+            24 => AdaptorID::Bridge24,
+            25 => AdaptorID::Bridge25,
+            26 => AdaptorID::Bridge26,
+            27 => AdaptorID::Bridge27,
+            28 => AdaptorID::Bridge28,
+            29 => AdaptorID::Bridge29,
+            30 => AdaptorID::Bridge30,
+            31 => AdaptorID::Bridge31,
+            32 => AdaptorID::Bridge32,
+            33 => AdaptorID::Bridge33,
+            34 => AdaptorID::Debridgedln,
+            _ => AdaptorID::Unknown(tag),
+        }
+    }
+
+    pub fn tag(&self) -> u8 {
+        match self {
+            AdaptorID::Bridge0 => 0,
+            AdaptorID::Bridge1 => 1,
+            AdaptorID::Bridge2 => 2,
+            AdaptorID::Bridge3 => 3,
+            AdaptorID::Bridge4 => 4,
+            AdaptorID::Bridge5 => 5,
+            AdaptorID::Bridge6 => 6,
+            AdaptorID::Bridge7 => 7,
+            AdaptorID::Bridge8 => 8,
+            AdaptorID::Bridge9 => 9,
+            AdaptorID::Bridge10 => 10,
+            AdaptorID::Bridge11 => 11,
+            AdaptorID::Bridge12 => 12,
+            AdaptorID::Bridge13 => 13,
+            AdaptorID::Bridge14 => 14,
+            AdaptorID::Bridge15 => 15,
+            AdaptorID::Bridge16 => 16,
+            AdaptorID::Bridge17 => 17,
+            AdaptorID::Cctp => 18,
+            AdaptorID::Bridge19 => 19,
+            AdaptorID::Bridge20 => 20,
+            AdaptorID::Wormhole => 21,
+            AdaptorID::Meson => 22,
+            AdaptorID::Bridge23 => 23,
+            AdaptorID::Bridge24 => 24,
+            AdaptorID::Bridge25 => 25,
+            AdaptorID::Bridge26 => 26,
+            AdaptorID::Bridge27 => 27,
+            AdaptorID::Bridge28 => 28,
+            AdaptorID::Bridge29 => 29,
+            AdaptorID::Bridge30 => 30,
+            AdaptorID::Bridge31 => 31,
+            AdaptorID::Bridge32 => 32,
+            AdaptorID::Bridge33 => 33,
+            AdaptorID::Debridgedln => 34,
+            AdaptorID::Unknown(tag) => *tag,
+        }
+    }
+}
 
+impl borsh::BorshDeserialize for AdaptorID {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let tag = u8::deserialize_reader(reader)?;
+        let value = AdaptorID::from_tag(tag);
+        if let AdaptorID::Unknown(tag) = value {
+            record(UnknownTagKind::AdaptorID, tag);
+        }
+        Ok(value)
+    }
+}
+
+impl borsh::BorshSerialize for AdaptorID {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.tag().serialize(writer)
+    }
+}
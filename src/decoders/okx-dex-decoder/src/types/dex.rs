@@ -1,9 +1,10 @@
+use carbon_core::borsh;
 
+use crate::unknown_tags::{record, UnknownTagKind};
 
-use carbon_core::{CarbonDeserialize, borsh};
-
-
-#[derive(CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
+/// Decoded by hand so that tags added on-chain after this list was written
+/// become `Unknown` instead of failing the whole instruction.
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
 pub enum Dex {
     SplTokenSwap,
     StableSwap,
@@ -28,6 +29,82 @@ pub enum Dex {
     SanctumRemoveLiq,
     SanctumNonWsolSwap,
     SanctumWsolSwap,
+    Unknown(u8),
 }
 
+impl Dex {
+    pub fn from_tag(tag: u8) -> Self {
+        match tag {
+            0 => Dex::SplTokenSwap,
+            1 => Dex::StableSwap,
+            2 => Dex::Whirlpool,
+            3 => Dex::MeteoraDynamicpool,
+            4 => Dex::RaydiumSwap,
+            5 => Dex::RaydiumStableSwap,
+            6 => Dex::RaydiumClmmSwap,
+            7 => Dex::AldrinExchangeV1,
+            8 => Dex::AldrinExchangeV2,
+            9 => Dex::LifinityV1,
+            10 => Dex::LifinityV2,
+            11 => Dex::RaydiumClmmSwapV2,
+            12 => Dex::FluxBeam,
+            13 => Dex::MeteoraDlmm,
+            14 => Dex::RaydiumCpmmSwap,
+            15 => Dex::OpenBookV2,
+            16 => Dex::WhirlpoolV2,
+            17 => Dex::Phoenix,
+            18 => Dex::ObricV2,
+            19 => Dex::SanctumAddLiq,
+            20 => Dex::SanctumRemoveLiq,
+            21 => Dex::SanctumNonWsolSwap,
+            22 => Dex::SanctumWsolSwap,
+            _ => Dex::Unknown(tag),
+        }
+    }
+
+    pub fn tag(&self) -> u8 {
+        match self {
+            Dex::SplTokenSwap => 0,
+            Dex::StableSwap => 1,
+            Dex::Whirlpool => 2,
+            Dex::MeteoraDynamicpool => 3,
+            Dex::RaydiumSwap => 4,
+            Dex::RaydiumStableSwap => 5,
+            Dex::RaydiumClmmSwap => 6,
+            Dex::AldrinExchangeV1 => 7,
+            Dex::AldrinExchangeV2 => 8,
+            Dex::LifinityV1 => 9,
+            Dex::LifinityV2 => 10,
+            Dex::RaydiumClmmSwapV2 => 11,
+            Dex::FluxBeam => 12,
+            Dex::MeteoraDlmm => 13,
+            Dex::RaydiumCpmmSwap => 14,
+            Dex::OpenBookV2 => 15,
+            Dex::WhirlpoolV2 => 16,
+            Dex::Phoenix => 17,
+            Dex::ObricV2 => 18,
+            Dex::SanctumAddLiq => 19,
+            Dex::SanctumRemoveLiq => 20,
+            Dex::SanctumNonWsolSwap => 21,
+            Dex::SanctumWsolSwap => 22,
+            Dex::Unknown(tag) => *tag,
+        }
+    }
+}
 
+impl borsh::BorshDeserialize for Dex {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let tag = u8::deserialize_reader(reader)?;
+        let value = Dex::from_tag(tag);
+        if let Dex::Unknown(tag) = value {
+            record(UnknownTagKind::Dex, tag);
+        }
+        Ok(value)
+    }
+}
+
+impl borsh::BorshSerialize for Dex {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.tag().serialize(writer)
+    }
+}
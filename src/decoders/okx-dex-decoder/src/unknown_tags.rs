@@ -0,0 +1,63 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// Enum whose on-chain tag was not recognised.
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum UnknownTagKind {
+    Dex,
+    AdaptorID,
+}
+
+type Hook = Arc<dyn Fn(UnknownTagKind, u8) + Send + Sync>;
+
+static DEX_COUNTS: [AtomicU64; 256] = [const { AtomicU64::new(0) }; 256];
+static ADAPTOR_ID_COUNTS: [AtomicU64; 256] = [const { AtomicU64::new(0) }; 256];
+static HOOK: RwLock<Option<Hook>> = RwLock::new(None);
+
+/// Registers a callback run every time an unknown tag is decoded, replacing
+/// any previous one. The callback runs outside the registry's lock, so it may
+/// itself set or clear the hook.
+pub fn set_unknown_tag_hook(hook: impl Fn(UnknownTagKind, u8) + Send + Sync + 'static) {
+    *HOOK
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Arc::new(hook));
+}
+
+pub fn clear_unknown_tag_hook() {
+    *HOOK
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+}
+
+/// How many times each unknown tag has been decoded since the process
+/// started, as `(kind, tag, count)`.
+pub fn unknown_tag_counts() -> Vec<(UnknownTagKind, u8, u64)> {
+    [
+        (UnknownTagKind::Dex, &DEX_COUNTS),
+        (UnknownTagKind::AdaptorID, &ADAPTOR_ID_COUNTS),
+    ]
+    .into_iter()
+    .flat_map(|(kind, counts)| {
+        counts.iter().enumerate().filter_map(move |(tag, count)| {
+            let count = count.load(Ordering::Relaxed);
+            (count > 0).then_some((kind, tag as u8, count))
+        })
+    })
+    .collect()
+}
+
+pub(crate) fn record(kind: UnknownTagKind, tag: u8) {
+    let counts = match kind {
+        UnknownTagKind::Dex => &DEX_COUNTS,
+        UnknownTagKind::AdaptorID => &ADAPTOR_ID_COUNTS,
+    };
+    counts[tag as usize].fetch_add(1, Ordering::Relaxed);
+
+    let hook = HOOK
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    if let Some(hook) = hook {
+        hook(kind, tag);
+    }
+}
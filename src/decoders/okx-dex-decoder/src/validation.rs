@@ -0,0 +1,106 @@
+use crate::instructions::OkxDexInstruction;
+use crate::types::{CommissionSwapArgs, Route, SwapArgs};
+
+/// Way in which a swap's split amounts or routes disagree with each other.
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash)]
+pub enum RouteViolation {
+    /// `amounts` and `routes` must pair up one to one.
+    AmountsLengthMismatch { amounts: usize, routes: usize },
+    /// The split amounts do not add up to the swap input.
+    AmountsSumMismatch { sum: u128, amount_in: u64 },
+    /// A route has no hops.
+    EmptyRoute { route: usize },
+    /// A hop lists a different number of DEXes and weights.
+    WeightsLengthMismatch {
+        route: usize,
+        hop: usize,
+        dexes: usize,
+        weights: usize,
+    },
+    /// A hop's weights do not add up to 100 percent.
+    WeightsSumMismatch { route: usize, hop: usize, sum: u32 },
+}
+
+/// Checks that `amounts` splits `amount_in` across `routes` and that every
+/// hop splits its input between its DEXes by weights adding up to 100.
+pub fn validate_routes(
+    amount_in: u64,
+    amounts: &[u64],
+    routes: &[Vec<Route>],
+) -> Vec<RouteViolation> {
+    let mut violations = Vec::new();
+
+    if amounts.len() != routes.len() {
+        violations.push(RouteViolation::AmountsLengthMismatch {
+            amounts: amounts.len(),
+            routes: routes.len(),
+        });
+    }
+
+    let sum: u128 = amounts.iter().map(|amount| *amount as u128).sum();
+    if sum != amount_in as u128 {
+        violations.push(RouteViolation::AmountsSumMismatch { sum, amount_in });
+    }
+
+    for (route_index, hops) in routes.iter().enumerate() {
+        if hops.is_empty() {
+            violations.push(RouteViolation::EmptyRoute { route: route_index });
+        }
+
+        for (hop_index, hop) in hops.iter().enumerate() {
+            if hop.dexes.len() != hop.weights.len() {
+                violations.push(RouteViolation::WeightsLengthMismatch {
+                    route: route_index,
+                    hop: hop_index,
+                    dexes: hop.dexes.len(),
+                    weights: hop.weights.len(),
+                });
+            }
+
+            let weight_sum: u32 = hop.weights.iter().map(|weight| *weight as u32).sum();
+            if weight_sum != 100 {
+                violations.push(RouteViolation::WeightsSumMismatch {
+                    route: route_index,
+                    hop: hop_index,
+                    sum: weight_sum,
+                });
+            }
+        }
+    }
+
+    violations
+}
+
+impl SwapArgs {
+    pub fn validate(&self) -> Vec<RouteViolation> {
+        validate_routes(self.amount_in, &self.amounts, &self.routes)
+    }
+}
+
+impl CommissionSwapArgs {
+    pub fn validate(&self) -> Vec<RouteViolation> {
+        validate_routes(self.amount_in, &self.amounts, &self.routes)
+    }
+}
+
+impl OkxDexInstruction {
+    /// Route violations of any swap instruction; empty for instructions that
+    /// carry no route.
+    pub fn route_violations(&self) -> Vec<RouteViolation> {
+        match self {
+            OkxDexInstruction::CommissionSolFromSwap(ix) => ix.args.validate(),
+            OkxDexInstruction::CommissionSolProxySwap(ix) => ix.data.validate(),
+            OkxDexInstruction::CommissionSolSwap(ix) => ix.data.validate(),
+            OkxDexInstruction::CommissionSolSwap2(ix) => ix.data.validate(),
+            OkxDexInstruction::CommissionSplFromSwap(ix) => ix.args.validate(),
+            OkxDexInstruction::CommissionSplProxySwap(ix) => ix.data.validate(),
+            OkxDexInstruction::CommissionSplSwap(ix) => ix.data.validate(),
+            OkxDexInstruction::CommissionSplSwap2(ix) => ix.data.validate(),
+            OkxDexInstruction::FromSwapLog(ix) => ix.args.validate(),
+            OkxDexInstruction::ProxySwap(ix) => ix.data.validate(),
+            OkxDexInstruction::Swap(ix) => ix.data.validate(),
+            OkxDexInstruction::Swap2(ix) => ix.data.validate(),
+            OkxDexInstruction::SwapEvent(_) => Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use carbon_okx_dex_decoder::legs::{expand_routes, SwapLeg};
+use carbon_okx_dex_decoder::types::{Dex, Route};
+
+fn hop(dexes: Vec<Dex>, weights: Vec<u8>) -> Route {
+    Route { dexes, weights }
+}
+
+fn leg(
+    route: usize,
+    hop: usize,
+    dex: Dex,
+    weight: u8,
+    share_bps: u64,
+    amount_in: Option<u64>,
+) -> SwapLeg {
+    SwapLeg {
+        route,
+        hop,
+        dex,
+        weight,
+        share_bps,
+        amount_in,
+        amount_out: None,
+    }
+}
+
+/// 1_000 split 600/400: the first route splits 70/30 and then goes through a
+/// second hop, the other is a single hop. Only first hops know their input.
+#[test]
+fn split_multi_hop_routes() {
+    let routes = vec![
+        vec![
+            hop(vec![Dex::Whirlpool, Dex::MeteoraDlmm], vec![70, 30]),
+            hop(vec![Dex::RaydiumClmmSwapV2], vec![100]),
+        ],
+        vec![hop(vec![Dex::RaydiumCpmmSwap], vec![100])],
+    ];
+
+    let legs = expand_routes(&[600, 400], &routes);
+
+    assert_eq!(
+        legs,
+        vec![
+            leg(0, 0, Dex::Whirlpool, 70, 4_200, Some(420)),
+            leg(0, 0, Dex::MeteoraDlmm, 30, 1_800, Some(180)),
+            leg(0, 1, Dex::RaydiumClmmSwapV2, 100, 6_000, None),
+            leg(1, 0, Dex::RaydiumCpmmSwap, 100, 4_000, Some(400)),
+        ]
+    );
+    let first_hops: u64 = legs
+        .iter()
+        .filter(|leg| leg.hop == 0)
+        .map(|leg| leg.share_bps)
+        .sum();
+    assert_eq!(first_hops, 10_000);
+}
+
+/// Shares and inputs round down, so the first hops can add up to less than
+/// the whole input.
+#[test]
+fn shares_and_inputs_round_down() {
+    let routes = vec![
+        vec![hop(vec![Dex::Whirlpool], vec![100])],
+        vec![hop(vec![Dex::MeteoraDlmm], vec![100])],
+        vec![hop(
+            vec![Dex::RaydiumCpmmSwap, Dex::Whirlpool],
+            vec![50, 50],
+        )],
+    ];
+
+    let legs = expand_routes(&[1, 1, 1], &routes);
+
+    assert_eq!(
+        legs,
+        vec![
+            leg(0, 0, Dex::Whirlpool, 100, 3_333, Some(1)),
+            leg(1, 0, Dex::MeteoraDlmm, 100, 3_333, Some(1)),
+            leg(2, 0, Dex::RaydiumCpmmSwap, 50, 1_666, Some(0)),
+            leg(2, 0, Dex::Whirlpool, 50, 1_666, Some(0)),
+        ]
+    );
+    let shares: u64 = legs.iter().map(|leg| leg.share_bps).sum();
+    assert_eq!(shares, 9_998);
+}
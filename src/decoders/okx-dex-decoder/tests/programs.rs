@@ -0,0 +1,116 @@
+use carbon_core::instruction::InstructionDecoder;
+use carbon_okx_dex_decoder::builder::BuildInstruction;
+use carbon_okx_dex_decoder::instructions::swap::{Swap, SwapInstructionAccounts};
+use carbon_okx_dex_decoder::programs::{OkxProgram, OkxPrograms};
+use carbon_okx_dex_decoder::types::{Dex, Route, SwapArgs};
+use carbon_okx_dex_decoder::{OkxDexDecoder, PROGRAM_ID};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+
+fn swap_instruction(program_id: Pubkey) -> Instruction {
+    let swap = Swap {
+        data: SwapArgs {
+            amount_in: 1_000,
+            expect_amount_out: 990,
+            min_return: 980,
+            amounts: vec![1_000],
+            routes: vec![vec![Route {
+                dexes: vec![Dex::Whirlpool],
+                weights: vec![100],
+            }]],
+        },
+    };
+    let accounts = SwapInstructionAccounts {
+        payer: Pubkey::new_unique(),
+        source_token_account: Pubkey::new_unique(),
+        destination_token_account: Pubkey::new_unique(),
+        source_mint: Pubkey::new_unique(),
+        destination_mint: Pubkey::new_unique(),
+    };
+    Instruction {
+        program_id,
+        ..swap.instruction(&accounts, &[])
+    }
+}
+
+fn devnet_program() -> OkxProgram {
+    OkxProgram {
+        program_id: Pubkey::new_unique(),
+        version: Some("devnet".to_string()),
+    }
+}
+
+#[test]
+fn default_set_is_the_mainnet_router() {
+    let programs = OkxPrograms::default();
+
+    assert!(programs.contains(&PROGRAM_ID));
+    assert_eq!(programs.programs().len(), 1);
+    assert_eq!(programs.version(&PROGRAM_ID), None);
+}
+
+#[test]
+fn register_adds_a_program_or_retags_it() {
+    let proxy = Pubkey::new_unique();
+    let mut programs = OkxPrograms::default();
+
+    programs.register(proxy, Some("v2"));
+    programs.register(proxy, Some("v3"));
+    programs.register(PROGRAM_ID, Some("v3"));
+
+    assert_eq!(programs.programs().len(), 2);
+    assert_eq!(programs.version(&proxy), Some("v3"));
+    assert_eq!(programs.version(&PROGRAM_ID), Some("v3"));
+}
+
+#[test]
+fn decoder_accepts_registered_programs() {
+    let proxy = Pubkey::new_unique();
+    let mut programs = OkxPrograms::default();
+    programs.register(proxy, None);
+    let decoder = OkxDexDecoder { programs };
+
+    assert!(decoder
+        .decode_instruction(&swap_instruction(PROGRAM_ID))
+        .is_some());
+    assert!(decoder
+        .decode_instruction(&swap_instruction(proxy))
+        .is_some());
+    assert!(OkxDexDecoder::default()
+        .decode_instruction(&swap_instruction(proxy))
+        .is_none());
+}
+
+#[test]
+fn replaced_set_drops_the_mainnet_router() {
+    let devnet = devnet_program();
+    let decoder = OkxDexDecoder {
+        programs: OkxPrograms::new(vec![devnet.clone()]),
+    };
+
+    assert!(decoder
+        .decode_instruction(&swap_instruction(devnet.program_id))
+        .is_some());
+    assert!(decoder
+        .decode_instruction(&swap_instruction(PROGRAM_ID))
+        .is_none());
+}
+
+#[test]
+fn set_filters_instructions_before_decoding() {
+    let devnet = devnet_program();
+    let programs = OkxPrograms::new(vec![devnet.clone()]);
+    let instructions = [
+        swap_instruction(PROGRAM_ID),
+        swap_instruction(devnet.program_id),
+        swap_instruction(Pubkey::new_unique()),
+    ];
+
+    let kept: Vec<Pubkey> = instructions
+        .iter()
+        .filter(|instruction| programs.contains(&instruction.program_id))
+        .map(|instruction| instruction.program_id)
+        .collect();
+
+    assert_eq!(kept, vec![devnet.program_id]);
+}
@@ -0,0 +1,238 @@
+use carbon_core::instruction::InstructionDecoder;
+use carbon_okx_dex_decoder::builder::BuildInstruction;
+use carbon_okx_dex_decoder::instructions::commission_sol_proxy_swap::{
+    CommissionSolProxySwap, CommissionSolProxySwapInstructionAccounts,
+};
+use carbon_okx_dex_decoder::instructions::commission_spl_from_swap::{
+    CommissionSplFromSwap, CommissionSplFromSwapInstructionAccounts,
+};
+use carbon_okx_dex_decoder::instructions::commission_spl_swap2::{
+    CommissionSplSwap2, CommissionSplSwap2InstructionAccounts,
+};
+use carbon_okx_dex_decoder::instructions::proxy_swap::{ProxySwap, ProxySwapInstructionAccounts};
+use carbon_okx_dex_decoder::instructions::swap::{Swap, SwapInstructionAccounts};
+use carbon_okx_dex_decoder::instructions::swap2::{Swap2, Swap2InstructionAccounts};
+use carbon_okx_dex_decoder::instructions::OkxDexInstruction;
+use carbon_okx_dex_decoder::types::{
+    AdaptorID, BridgeToArgs, CommissionSwapArgs, Dex, Route, SwapArgs, SwapType,
+};
+use carbon_okx_dex_decoder::OkxDexDecoder;
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::pubkey::Pubkey;
+
+fn swap_args() -> SwapArgs {
+    SwapArgs {
+        amount_in: 1_000_000,
+        expect_amount_out: 990_000,
+        min_return: 985_000,
+        amounts: vec![600_000, 400_000],
+        routes: vec![
+            vec![
+                Route {
+                    dexes: vec![Dex::Whirlpool, Dex::MeteoraDlmm],
+                    weights: vec![70, 30],
+                },
+                Route {
+                    dexes: vec![Dex::RaydiumClmmSwapV2],
+                    weights: vec![100],
+                },
+            ],
+            vec![Route {
+                dexes: vec![Dex::RaydiumCpmmSwap],
+                weights: vec![100],
+            }],
+        ],
+    }
+}
+
+fn remaining_accounts() -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(Pubkey::new_unique(), false),
+        AccountMeta::new(Pubkey::new_unique(), false),
+    ]
+}
+
+/// Builds `instruction`, decodes it again and checks that the arguments,
+/// the named accounts and the remaining accounts survive unchanged.
+fn assert_round_trip<T>(
+    instruction: T,
+    accounts: T::ArrangedAccounts,
+    wrap: fn(T) -> OkxDexInstruction,
+) where
+    T: BuildInstruction + Clone,
+    T::ArrangedAccounts: PartialEq + std::fmt::Debug,
+{
+    let remaining = remaining_accounts();
+    let built = instruction.instruction(&accounts, &remaining);
+
+    let decoded = OkxDexDecoder::default()
+        .decode_instruction(&built)
+        .expect("built instruction decodes");
+    assert_eq!(decoded.data, wrap(instruction));
+    assert_eq!(T::arrange_accounts(&built.accounts), Some(accounts));
+    assert!(built.accounts.ends_with(&remaining));
+    assert!(built.accounts[0].is_signer);
+}
+
+#[test]
+fn swap_round_trips() {
+    let accounts = SwapInstructionAccounts {
+        payer: Pubkey::new_unique(),
+        source_token_account: Pubkey::new_unique(),
+        destination_token_account: Pubkey::new_unique(),
+        source_mint: Pubkey::new_unique(),
+        destination_mint: Pubkey::new_unique(),
+    };
+    assert_round_trip(
+        Swap { data: swap_args() },
+        accounts,
+        OkxDexInstruction::Swap,
+    );
+}
+
+#[test]
+fn swap2_round_trips() {
+    let accounts = Swap2InstructionAccounts {
+        payer: Pubkey::new_unique(),
+        source_token_account: Pubkey::new_unique(),
+        destination_token_account: Pubkey::new_unique(),
+        source_mint: Pubkey::new_unique(),
+        destination_mint: Pubkey::new_unique(),
+    };
+    assert_round_trip(
+        Swap2 {
+            data: swap_args(),
+            order_id: 42,
+        },
+        accounts,
+        OkxDexInstruction::Swap2,
+    );
+}
+
+#[test]
+fn proxy_swap_round_trips() {
+    let accounts = ProxySwapInstructionAccounts {
+        payer: Pubkey::new_unique(),
+        source_token_account: Pubkey::new_unique(),
+        destination_token_account: Pubkey::new_unique(),
+        source_mint: Pubkey::new_unique(),
+        destination_mint: Pubkey::new_unique(),
+        sa_authority: Pubkey::new_unique(),
+        source_token_sa: Pubkey::new_unique(),
+        destination_token_sa: Pubkey::new_unique(),
+        source_token_program: spl_token_program(),
+        destination_token_program: spl_token_program(),
+        associated_token_program: Pubkey::new_unique(),
+        system_program: solana_sdk::system_program::ID,
+    };
+    assert_round_trip(
+        ProxySwap {
+            data: swap_args(),
+            order_id: 7,
+        },
+        accounts,
+        OkxDexInstruction::ProxySwap,
+    );
+}
+
+#[test]
+fn commission_spl_swap2_round_trips() {
+    let args = swap_args();
+    let accounts = CommissionSplSwap2InstructionAccounts {
+        payer: Pubkey::new_unique(),
+        source_token_account: Pubkey::new_unique(),
+        destination_token_account: Pubkey::new_unique(),
+        source_mint: Pubkey::new_unique(),
+        destination_mint: Pubkey::new_unique(),
+        commission_token_account: Pubkey::new_unique(),
+        token_program: spl_token_program(),
+    };
+    assert_round_trip(
+        CommissionSplSwap2 {
+            data: CommissionSwapArgs {
+                amount_in: args.amount_in,
+                expect_amount_out: args.expect_amount_out,
+                min_return: args.min_return,
+                amounts: args.amounts,
+                routes: args.routes,
+                commission_rate: 100,
+                commission_direction: false,
+            },
+            order_id: 9,
+        },
+        accounts,
+        OkxDexInstruction::CommissionSplSwap2,
+    );
+}
+
+#[test]
+fn commission_sol_proxy_swap_round_trips() {
+    let accounts = CommissionSolProxySwapInstructionAccounts {
+        payer: Pubkey::new_unique(),
+        source_token_account: Pubkey::new_unique(),
+        destination_token_account: Pubkey::new_unique(),
+        source_mint: Pubkey::new_unique(),
+        destination_mint: Pubkey::new_unique(),
+        commission_account: Pubkey::new_unique(),
+        sa_authority: Pubkey::new_unique(),
+        source_token_sa: Pubkey::new_unique(),
+        destination_token_sa: Pubkey::new_unique(),
+        source_token_program: spl_token_program(),
+        destination_token_program: spl_token_program(),
+        associated_token_program: Pubkey::new_unique(),
+        system_program: solana_sdk::system_program::ID,
+    };
+    assert_round_trip(
+        CommissionSolProxySwap {
+            data: swap_args(),
+            commission_rate: 30,
+            commission_direction: true,
+            order_id: 11,
+        },
+        accounts,
+        OkxDexInstruction::CommissionSolProxySwap,
+    );
+}
+
+#[test]
+fn commission_spl_from_swap_round_trips_bridge_args_and_unknown_tags() {
+    let mut args = swap_args();
+    args.routes[1][0].dexes = vec![Dex::Unknown(200)];
+    let accounts = CommissionSplFromSwapInstructionAccounts {
+        payer: Pubkey::new_unique(),
+        source_token_account: Pubkey::new_unique(),
+        destination_token_account: Pubkey::new_unique(),
+        source_mint: Pubkey::new_unique(),
+        destination_mint: Pubkey::new_unique(),
+        bridge_program: Pubkey::new_unique(),
+        associated_token_program: Pubkey::new_unique(),
+        token_program: spl_token_program(),
+        token_2022_program: Pubkey::new_unique(),
+        system_program: solana_sdk::system_program::ID,
+        commission_token_account: Pubkey::new_unique(),
+    };
+    assert_round_trip(
+        CommissionSplFromSwap {
+            args,
+            commission_rate: 50,
+            bridge_to_args: BridgeToArgs {
+                adaptor_id: AdaptorID::Unknown(250),
+                to: vec![0xab; 20],
+                order_id: 3,
+                to_chain_id: 8453,
+                amount: 985_000,
+                swap_type: SwapType::SWAPANDBRIDGE,
+                data: vec![1, 2, 3],
+                ext_data: vec![],
+            },
+            offset: 4,
+            len: 8,
+        },
+        accounts,
+        OkxDexInstruction::CommissionSplFromSwap,
+    );
+}
+
+fn spl_token_program() -> Pubkey {
+    solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")
+}
@@ -0,0 +1,23 @@
+use carbon_core::borsh::BorshDeserialize;
+use carbon_okx_dex_decoder::types::Dex;
+use carbon_okx_dex_decoder::unknown_tags::{
+    clear_unknown_tag_hook, set_unknown_tag_hook, UnknownTagKind,
+};
+use std::sync::{Arc, Mutex};
+
+/// A hook that unregisters itself runs once and does not deadlock. Kept in
+/// its own test binary, as the hook is process-wide.
+#[test]
+fn hook_may_clear_itself() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&seen);
+    set_unknown_tag_hook(move |kind, tag| {
+        recorded.lock().unwrap().push((kind, tag));
+        clear_unknown_tag_hook();
+    });
+
+    assert_eq!(Dex::try_from_slice(&[201]).unwrap(), Dex::Unknown(201));
+    assert_eq!(Dex::try_from_slice(&[202]).unwrap(), Dex::Unknown(202));
+
+    assert_eq!(*seen.lock().unwrap(), vec![(UnknownTagKind::Dex, 201)]);
+}
@@ -0,0 +1,28 @@
+use carbon_core::borsh::BorshDeserialize;
+use carbon_okx_dex_decoder::types::{AdaptorID, Dex};
+use carbon_okx_dex_decoder::unknown_tags::{unknown_tag_counts, UnknownTagKind};
+
+fn count(kind: UnknownTagKind, tag: u8) -> u64 {
+    unknown_tag_counts()
+        .into_iter()
+        .find(|(counted_kind, counted_tag, _)| *counted_kind == kind && *counted_tag == tag)
+        .map_or(0, |(_, _, count)| count)
+}
+
+#[test]
+fn only_decoded_tags_are_counted() {
+    assert_eq!(Dex::from_tag(200), Dex::Unknown(200));
+    assert_eq!(AdaptorID::from_tag(250), AdaptorID::Unknown(250));
+    assert_eq!(count(UnknownTagKind::Dex, 200), 0);
+    assert_eq!(count(UnknownTagKind::AdaptorID, 250), 0);
+
+    assert_eq!(Dex::try_from_slice(&[200]).unwrap(), Dex::Unknown(200));
+    assert_eq!(
+        AdaptorID::try_from_slice(&[250]).unwrap(),
+        AdaptorID::Unknown(250)
+    );
+    assert_eq!(Dex::try_from_slice(&[2]).unwrap(), Dex::Whirlpool);
+    assert_eq!(count(UnknownTagKind::Dex, 200), 1);
+    assert_eq!(count(UnknownTagKind::AdaptorID, 250), 1);
+    assert_eq!(count(UnknownTagKind::Dex, 2), 0);
+}
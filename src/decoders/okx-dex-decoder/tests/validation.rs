@@ -0,0 +1,133 @@
+use carbon_core::instruction::InstructionDecoder;
+use carbon_okx_dex_decoder::builder::BuildInstruction;
+use carbon_okx_dex_decoder::instructions::swap::{Swap, SwapInstructionAccounts};
+use carbon_okx_dex_decoder::types::{Dex, Route, SwapArgs};
+use carbon_okx_dex_decoder::validation::{validate_routes, RouteViolation};
+use carbon_okx_dex_decoder::OkxDexDecoder;
+use solana_sdk::pubkey::Pubkey;
+
+fn hop(dexes: Vec<Dex>, weights: Vec<u8>) -> Route {
+    Route { dexes, weights }
+}
+
+/// 1_000 split 600/400 over a two-hop route and a single-hop one.
+fn valid_routes() -> Vec<Vec<Route>> {
+    vec![
+        vec![
+            hop(vec![Dex::Whirlpool, Dex::MeteoraDlmm], vec![70, 30]),
+            hop(vec![Dex::RaydiumClmmSwapV2], vec![100]),
+        ],
+        vec![hop(vec![Dex::RaydiumCpmmSwap], vec![100])],
+    ]
+}
+
+#[test]
+fn consistent_routes_have_no_violations() {
+    assert!(validate_routes(1_000, &[600, 400], &valid_routes()).is_empty());
+}
+
+#[test]
+fn amounts_must_pair_up_with_routes() {
+    let violations = validate_routes(1_000, &[1_000], &valid_routes());
+
+    assert_eq!(
+        violations,
+        vec![RouteViolation::AmountsLengthMismatch {
+            amounts: 1,
+            routes: 2
+        }]
+    );
+}
+
+/// The sum is widened so that amounts overflowing `u64` are still reported.
+#[test]
+fn amounts_must_add_up_to_the_input() {
+    let violations = validate_routes(1_000, &[u64::MAX, 400], &valid_routes());
+
+    assert_eq!(
+        violations,
+        vec![RouteViolation::AmountsSumMismatch {
+            sum: u64::MAX as u128 + 400,
+            amount_in: 1_000
+        }]
+    );
+}
+
+#[test]
+fn routes_need_a_hop() {
+    let mut routes = valid_routes();
+    routes[1].clear();
+
+    let violations = validate_routes(1_000, &[600, 400], &routes);
+
+    assert_eq!(violations, vec![RouteViolation::EmptyRoute { route: 1 }]);
+}
+
+#[test]
+fn every_dex_of_a_hop_needs_a_weight() {
+    let mut routes = valid_routes();
+    routes[0][0].weights = vec![100];
+
+    let violations = validate_routes(1_000, &[600, 400], &routes);
+
+    assert_eq!(
+        violations,
+        vec![RouteViolation::WeightsLengthMismatch {
+            route: 0,
+            hop: 0,
+            dexes: 2,
+            weights: 1
+        }]
+    );
+}
+
+#[test]
+fn weights_must_add_up_to_100() {
+    let mut routes = valid_routes();
+    routes[0][1].weights = vec![90];
+
+    let violations = validate_routes(1_000, &[600, 400], &routes);
+
+    assert_eq!(
+        violations,
+        vec![RouteViolation::WeightsSumMismatch {
+            route: 0,
+            hop: 1,
+            sum: 90
+        }]
+    );
+}
+
+/// Decoding keeps malformed routes; the violations are read off the
+/// decoded instruction.
+#[test]
+fn decoded_swap_reports_its_violations() {
+    let swap = Swap {
+        data: SwapArgs {
+            amount_in: 1_000,
+            expect_amount_out: 990,
+            min_return: 980,
+            amounts: vec![600, 300],
+            routes: valid_routes(),
+        },
+    };
+    let accounts = SwapInstructionAccounts {
+        payer: Pubkey::new_unique(),
+        source_token_account: Pubkey::new_unique(),
+        destination_token_account: Pubkey::new_unique(),
+        source_mint: Pubkey::new_unique(),
+        destination_mint: Pubkey::new_unique(),
+    };
+
+    let decoded = OkxDexDecoder::default()
+        .decode_instruction(&swap.instruction(&accounts, &[]))
+        .unwrap();
+
+    assert_eq!(
+        decoded.data.route_violations(),
+        vec![RouteViolation::AmountsSumMismatch {
+            sum: 900,
+            amount_in: 1_000
+        }]
+    );
+}
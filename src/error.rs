@@ -0,0 +1,168 @@
+use carbon_core::deserialize::{ArrangeAccounts, CarbonDeserialize};
+use serde::{Deserialize, Serialize};
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::pubkey::Pubkey;
+use std::any::type_name;
+use std::fmt;
+
+use crate::protocol::Protocol;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The program ID does not belong to any registered protocol.
+    UnsupportedProgram(Pubkey),
+    /// A protocol-specific decoder was handed another program's instruction.
+    WrongProgram { expected: Pubkey, actual: Pubkey },
+    /// The data does not start with the discriminator of any instruction we
+    /// decode for this protocol.
+    UnknownDiscriminator { protocol: Protocol },
+    /// The discriminator matched but the borsh payload did not deserialize.
+    Deserialize {
+        protocol: Protocol,
+        instruction: String,
+    },
+    /// The instruction carries too few accounts for its layout.
+    NotEnoughAccounts {
+        instruction: String,
+        expected: usize,
+        actual: usize,
+    },
+    /// The transaction is neither binary-encoded nor raw JSON.
+    UnsupportedEncoding,
+    /// The transaction payload itself is malformed.
+    InvalidTransaction(String),
+    /// A compiled instruction points past the end of the account list.
+    AccountIndexOutOfBounds { index: u8 },
+    /// A v0 transaction references a lookup table that could not be loaded.
+    MissingLookupTable(Pubkey),
+    /// A lookup references an index past the end of its table.
+    LookupTableIndexOutOfBounds { table: Pubkey, index: u8 },
+    /// An account snapshot file could not be read or parsed.
+    InvalidSnapshot(String),
+    /// The instruction decoded fine but is not mapped to a swap.
+    UnsupportedVariant {
+        protocol: Protocol,
+        instruction: String,
+    },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnsupportedProgram(program_id) => {
+                write!(f, "unsupported program {program_id}")
+            }
+            DecodeError::WrongProgram { expected, actual } => {
+                write!(f, "expected program {expected}, got {actual}")
+            }
+            DecodeError::UnknownDiscriminator { protocol } => {
+                write!(f, "unknown {protocol:?} instruction discriminator")
+            }
+            DecodeError::Deserialize {
+                protocol,
+                instruction,
+            } => write!(f, "failed to deserialize {protocol:?} {instruction}"),
+            DecodeError::NotEnoughAccounts {
+                instruction,
+                expected,
+                actual,
+            } => write!(f, "{instruction} expects {expected} accounts, got {actual}"),
+            DecodeError::UnsupportedEncoding => write!(f, "unsupported transaction encoding"),
+            DecodeError::InvalidTransaction(reason) => write!(f, "invalid transaction: {reason}"),
+            DecodeError::AccountIndexOutOfBounds { index } => {
+                write!(f, "account index {index} is out of bounds")
+            }
+            DecodeError::MissingLookupTable(table) => {
+                write!(f, "address lookup table {table} is not available")
+            }
+            DecodeError::LookupTableIndexOutOfBounds { table, index } => {
+                write!(f, "index {index} is out of bounds for lookup table {table}")
+            }
+            DecodeError::InvalidSnapshot(reason) => write!(f, "invalid snapshot: {reason}"),
+            DecodeError::UnsupportedVariant {
+                protocol,
+                instruction,
+            } => write!(f, "{protocol:?} {instruction} is not supported"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+pub(crate) fn check_program(expected: Pubkey, actual: Pubkey) -> Result<(), DecodeError> {
+    if expected != actual {
+        return Err(DecodeError::WrongProgram { expected, actual });
+    }
+    Ok(())
+}
+
+/// Explains why a carbon decoder returned `None`: either the data carries one
+/// of the `known` discriminators and its payload is broken, or it is an
+/// instruction we do not know at all.
+pub(crate) fn decode_failure(
+    protocol: Protocol,
+    data: &[u8],
+    known: &[(&str, &[u8])],
+) -> DecodeError {
+    match known
+        .iter()
+        .find(|(_, discriminator)| data.starts_with(discriminator))
+    {
+        Some((instruction, _)) => DecodeError::Deserialize {
+            protocol,
+            instruction: instruction.to_string(),
+        },
+        None => DecodeError::UnknownDiscriminator { protocol },
+    }
+}
+
+/// Name and discriminator of an instruction a protocol mapping handles.
+pub(crate) fn known<T: CarbonDeserialize>() -> (&'static str, &'static [u8]) {
+    (short_type_name::<T>(), T::DISCRIMINATOR)
+}
+
+pub(crate) fn unsupported_variant(
+    protocol: Protocol,
+    instruction_type: impl fmt::Debug,
+) -> DecodeError {
+    DecodeError::UnsupportedVariant {
+        protocol,
+        instruction: format!("{instruction_type:?}"),
+    }
+}
+
+/// `ArrangeAccounts::arrange_accounts` that reports how many accounts were
+/// missing instead of returning `None`.
+pub(crate) fn arrange_accounts<T: ArrangeAccounts>(
+    accounts: &[AccountMeta],
+) -> Result<T::ArrangedAccounts, DecodeError> {
+    T::arrange_accounts(accounts).ok_or_else(|| DecodeError::NotEnoughAccounts {
+        instruction: short_type_name::<T>().to_string(),
+        expected: required_accounts::<T>(accounts.len()),
+        actual: accounts.len(),
+    })
+}
+
+/// Upper bound for the account-count probe; no supported layout comes close.
+const MAX_ACCOUNTS: usize = 64;
+
+/// Smallest account count above `actual` that `T` accepts. Some layouts only
+/// accept exact counts, so fall back to the smallest accepted count overall.
+fn required_accounts<T: ArrangeAccounts>(actual: usize) -> usize {
+    let placeholder = AccountMeta::new_readonly(Pubkey::default(), false);
+    let accepted: Vec<usize> = (0..=MAX_ACCOUNTS)
+        .filter(|len| T::arrange_accounts(&vec![placeholder.clone(); *len]).is_some())
+        .collect();
+
+    accepted
+        .iter()
+        .find(|len| **len > actual)
+        .or(accepted.first())
+        .copied()
+        .unwrap_or(MAX_ACCOUNTS)
+}
+
+pub(crate) fn short_type_name<T>() -> &'static str {
+    let name = type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiquidityAction {
+    CreatePool,
+    Deposit,
+    Withdraw,
+}
+
+/// A pool creation, deposit or withdrawal. For deposits the base/quote
+/// amounts are the maxima the user allowed, for withdrawals the minima they
+/// accepted, and for pool creation the exact seed liquidity.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LiquidityEvent {
+    pub action: LiquidityAction,
+    pub pool: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub lp_mint: Pubkey,
+    /// LP tokens minted by a deposit or burned by a withdrawal.
+    pub lp_amount: Option<u64>,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleStage {
+    /// The token was created on a bonding curve.
+    Launched,
+    /// The curve completed and liquidity moved to a Raydium AMM v4 pool.
+    MigratedToAmm,
+    /// The curve completed and liquidity moved to a Raydium CPMM pool.
+    MigratedToCpswap,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TokenMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub decimals: u8,
+}
+
+/// A step in a launchpad token's life, from creation to graduation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LifecycleEvent {
+    pub stage: LifecycleStage,
+    /// The launchpad pool holding the bonding curve.
+    pub pool: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    /// Pool the liquidity graduated to, for migrations.
+    pub destination_pool: Option<Pubkey>,
+    /// Token metadata, set when the token is launched.
+    pub metadata: Option<TokenMetadata>,
+}
@@ -1,33 +1,34 @@
-use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::ArrangeAccounts;
 use carbon_core::instruction::InstructionDecoder;
-use carbon_core::{borsh, CarbonDeserialize};
-use carbon_jupiter_swap_decoder::accounts::JupiterSwapAccount;
 use carbon_jupiter_swap_decoder::instructions::exact_out_route::ExactOutRoute;
 use carbon_jupiter_swap_decoder::instructions::route::Route;
+use carbon_jupiter_swap_decoder::instructions::route_with_token_ledger::RouteWithTokenLedger;
 use carbon_jupiter_swap_decoder::instructions::shared_accounts_exact_out_route::SharedAccountsExactOutRoute;
 use carbon_jupiter_swap_decoder::instructions::shared_accounts_route::SharedAccountsRoute;
-use carbon_jupiter_swap_decoder::instructions::swap_event::SwapEvent;
+use carbon_jupiter_swap_decoder::instructions::shared_accounts_route_with_token_ledger::SharedAccountsRouteWithTokenLedger;
 use carbon_jupiter_swap_decoder::instructions::JupiterSwapInstruction;
-use carbon_jupiter_swap_decoder::types;
 use carbon_jupiter_swap_decoder::JupiterSwapDecoder;
-use carbon_okx_dex_decoder::accounts::OkxDexAccount;
+use carbon_okx_dex_decoder::dex_programs::RouteMismatch;
 use carbon_okx_dex_decoder::instructions::commission_spl_swap::CommissionSplSwap;
 use carbon_okx_dex_decoder::instructions::commission_spl_swap2::CommissionSplSwap2;
 use carbon_okx_dex_decoder::instructions::swap::Swap;
 use carbon_okx_dex_decoder::instructions::swap2::Swap2;
 use carbon_okx_dex_decoder::instructions::{
     commission_sol_from_swap, commission_sol_proxy_swap, commission_sol_swap, commission_sol_swap2,
-    commission_spl_from_swap, commission_spl_proxy_swap, from_swap_log, proxy_swap,
+    commission_spl_from_swap, commission_spl_proxy_swap, from_swap_log, proxy_swap, swap_event,
     OkxDexInstruction,
 };
+use carbon_okx_dex_decoder::legs::SwapLeg;
+use carbon_okx_dex_decoder::programs::OkxPrograms;
+use carbon_okx_dex_decoder::types::SwapEvent;
+use carbon_okx_dex_decoder::validation::RouteViolation;
 use carbon_okx_dex_decoder::OkxDexDecoder;
-use carbon_pump_swap_decoder::accounts::PumpSwapAccount;
 use carbon_pump_swap_decoder::instructions::buy::Buy;
+use carbon_pump_swap_decoder::instructions::create_pool::CreatePool;
+use carbon_pump_swap_decoder::instructions::deposit::Deposit;
 use carbon_pump_swap_decoder::instructions::sell::Sell;
+use carbon_pump_swap_decoder::instructions::withdraw::Withdraw;
 use carbon_pump_swap_decoder::instructions::PumpSwapInstruction;
 use carbon_pump_swap_decoder::PumpSwapDecoder;
-use carbon_pumpfun_decoder::accounts::PumpfunAccount;
 use carbon_pumpfun_decoder::instructions::create::Create;
 use carbon_pumpfun_decoder::instructions::PumpfunInstruction;
 use carbon_pumpfun_decoder::PumpfunDecoder;
@@ -38,19 +39,73 @@ use carbon_raydium_amm_v4_decoder::RaydiumAmmV4Decoder;
 
 use carbon_raydium_launchpad_decoder::instructions::buy_exact_in::BuyExactIn;
 use carbon_raydium_launchpad_decoder::instructions::buy_exact_out::BuyExactOut;
+use carbon_raydium_launchpad_decoder::instructions::initialize::Initialize;
+use carbon_raydium_launchpad_decoder::instructions::migrate_to_amm::MigrateToAmm;
+use carbon_raydium_launchpad_decoder::instructions::migrate_to_cpswap::MigrateToCpswap;
+use carbon_raydium_launchpad_decoder::instructions::sell_exact_in::SellExactIn;
+use carbon_raydium_launchpad_decoder::instructions::sell_exact_out::SellExactOut;
 use carbon_raydium_launchpad_decoder::instructions::RaydiumLaunchpadInstruction;
 use carbon_raydium_launchpad_decoder::RaydiumLaunchpadDecoder;
 use serde::{Deserialize, Serialize};
 use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey;
 use solana_sdk::pubkey::Pubkey;
-use std::any::type_name;
-use std::str::FromStr;
 
-#[derive(Serialize, Deserialize, Debug)]
+pub mod balances;
+pub mod bridge;
+pub mod commission;
+pub mod error;
+pub mod events;
+pub mod lookup_table;
+pub mod pool_state;
+pub mod protocol;
+pub mod route_plan;
+mod token_ledger;
+pub mod transaction;
+
+pub use bridge::BridgeTransfer;
+use commission::okx_commission_on_net;
+pub use commission::{Commission, FeeSide};
+pub use error::DecodeError;
+use error::{arrange_accounts, check_program, decode_failure, known, unsupported_variant};
+pub use events::{LifecycleEvent, LifecycleStage, LiquidityAction, LiquidityEvent, TokenMetadata};
+pub use lookup_table::LookupTableResolver;
+pub use pool_state::{PoolStateCache, PoolStateProvider};
+pub use protocol::{Protocol, PROTOCOLS};
+use route_plan::jupiter_hops;
+pub use route_plan::JupiterHop;
+pub use transaction::{
+    decode_compiled_instruction, decode_transaction, decode_transaction_with_context,
+    decode_transaction_with_resolver, DecodeContext, InstructionFailure, InstructionSwap,
+    MessageAccounts, TransactionSwaps,
+};
+
+const WSOL_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapMode {
+    /// The input amount is fixed and the output has a lower bound.
+    ExactIn,
+    /// The output amount is fixed and the input has an upper bound.
+    ExactOut,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct SwapTransaction {
     pub amm: Option<Pubkey>,
-    pub in_amount: u64,
-    pub out_amount: Option<u64>,
+    pub swap_mode: Option<SwapMode>,
+    /// Exact input of an exact-in swap.
+    pub amount_in: Option<u64>,
+    /// Input limit of an exact-out swap.
+    pub max_amount_in: Option<u64>,
+    /// Exact output of an exact-out swap.
+    pub amount_out: Option<u64>,
+    /// Output limit of an exact-in swap.
+    pub min_amount_out: Option<u64>,
+    /// Input the router quoted for an exact-out swap, before slippage.
+    pub quoted_amount_in: Option<u64>,
+    /// Output the router quoted for an exact-in swap, before slippage.
+    pub quoted_amount_out: Option<u64>,
     pub mint_token_in: Option<Pubkey>,
     pub mint_token_out: Option<Pubkey>,
     pub mint_token_account_in: Option<Pubkey>,
@@ -59,76 +114,249 @@ pub struct SwapTransaction {
     pub mint: Option<Pubkey>,
     pub create_instruction_accounts:
         Option<carbon_pumpfun_decoder::instructions::create::CreateInstructionAccounts>,
+    /// Amount that actually left the source account, from the transaction
+    /// meta. Only set by the transaction-level decoder.
+    pub realized_in: Option<u64>,
+    /// Amount that actually reached the destination account.
+    pub realized_out: Option<u64>,
+    pub liquidity: Option<LiquidityEvent>,
+    pub lifecycle: Option<LifecycleEvent>,
+    /// Per-DEX legs of an OKX route, in route and hop order.
+    pub okx_legs: Vec<SwapLeg>,
+    /// Inconsistencies between an OKX swap's amounts and routes. Swaps with
+    /// violations decode anyway so callers can decide whether to trust them.
+    pub route_violations: Vec<RouteViolation>,
+    /// DEXes an OKX route declares but never invoked, and programs it invoked
+    /// without declaring. Only filled by the transaction-level decoder.
+    pub route_mismatches: Vec<RouteMismatch>,
+    /// Hops of a Jupiter route plan, in execution order.
+    pub jupiter_hops: Vec<JupiterHop>,
+    /// Token ledger a Jupiter route reads its input from. The input is only
+    /// known once the transaction ran, so `amount_in` is filled by the
+    /// transaction-level decoder.
+    pub token_ledger: Option<Pubkey>,
+    pub commission: Option<Commission>,
+    /// Cross-chain transfer the swap output is sent into.
+    pub bridge: Option<BridgeTransfer>,
 }
 
-pub fn decode_raydium_instruction(
+/// Decodes an instruction from any supported program, picking the decoder
+/// from the program ID. Plain pubkeys carry no signer flags, so `user` is
+/// only set for layouts that name the wallet; use
+/// [`decode_instruction_with_metas`] to fall back to the signing account.
+pub fn decode_instruction(
     data: Vec<u8>,
     accounts: Vec<Pubkey>,
     program_id: Pubkey,
-) -> Option<SwapTransaction> {
-    let decoder: RaydiumAmmV4Decoder = RaydiumAmmV4Decoder;
+) -> Result<SwapTransaction, DecodeError> {
+    decode_instruction_with_metas(data, readonly_metas(&accounts), program_id)
+}
 
-    let account_metas: Vec<AccountMeta> = accounts
+/// Plain pubkeys as non-signing, read-only account metas.
+fn readonly_metas(accounts: &[Pubkey]) -> Vec<AccountMeta> {
+    accounts
         .iter()
-        .map(|pubkey| AccountMeta {
-            pubkey: *pubkey,
-            is_signer: false,   // Defina como true se a conta for um signatário
-            is_writable: false, // Defina como true se a conta for gravável
-        })
-        .collect();
+        .map(|pubkey| AccountMeta::new_readonly(*pubkey, false))
+        .collect()
+}
+
+/// Like [`decode_instruction`], but keeps the signer and writable flags of
+/// the accounts. When a layout does not name the trading wallet, `user` is
+/// the first signing account.
+pub fn decode_instruction_with_metas(
+    data: Vec<u8>,
+    accounts: Vec<AccountMeta>,
+    program_id: Pubkey,
+) -> Result<SwapTransaction, DecodeError> {
+    decode_swap_with_metas(data, accounts, program_id, &OkxPrograms::default())
+        .map(|(swap, _)| swap)
+}
+
+/// Like [`decode_instruction_with_metas`] for the OKX router deployments in
+/// `okx_programs`, also handing back the OKX swap event the instruction
+/// carried, if it was one.
+pub(crate) fn decode_swap_with_metas(
+    data: Vec<u8>,
+    accounts: Vec<AccountMeta>,
+    program_id: Pubkey,
+    okx_programs: &OkxPrograms,
+) -> Result<(SwapTransaction, Option<SwapEvent>), DecodeError> {
+    let signer = accounts
+        .iter()
+        .find(|account| account.is_signer)
+        .map(|account| account.pubkey);
+    let protocol = Protocol::from_program_id_in(&program_id, okx_programs)
+        .ok_or(DecodeError::UnsupportedProgram(program_id))?;
+
+    let (mut swap, okx_event) = match protocol {
+        Protocol::RaydiumAmmV4 => decode_raydium_instruction_with_metas(data, accounts, program_id)
+            .map(|swap| (swap, None)),
+        Protocol::Pumpfun => decode_pumpfun_instruction_with_metas(data, accounts, program_id)
+            .map(|swap| (swap, None)),
+        Protocol::PumpSwap => decode_pumpswap_instruction_with_metas(data, accounts, program_id)
+            .map(|swap| (swap, None)),
+        Protocol::Jupiter => decode_jupiter_instruction_with_metas(data, accounts, program_id)
+            .map(|swap| (swap, None)),
+        Protocol::Okx => decode_okx_swap(data, accounts, program_id, okx_programs),
+        Protocol::RaydiumLaunchpad => {
+            decode_raydiumlaunchpad_instruction_with_metas(data, accounts, program_id)
+                .map(|swap| (swap, None))
+        }
+    }?;
+
+    if swap.user.is_none() {
+        swap.user = signer;
+    }
+    Ok((swap, okx_event))
+}
+
+/// Lowest output an exact-in route accepts: the quote minus slippage.
+fn min_amount_out(quoted_out_amount: u64, slippage_bps: u16) -> u64 {
+    let amount =
+        quoted_out_amount as u128 * 10_000u128.saturating_sub(slippage_bps as u128) / 10_000;
+    amount as u64
+}
+
+/// Output account of a Jupiter route without shared accounts. Its
+/// `destination_token_account` is optional and overrides the user's own
+/// account; when unset the Jupiter program ID stands in for it.
+fn jupiter_destination(
+    user_destination_token_account: Pubkey,
+    destination_token_account: Pubkey,
+) -> Pubkey {
+    if destination_token_account == carbon_jupiter_swap_decoder::PROGRAM_ID {
+        user_destination_token_account
+    } else {
+        destination_token_account
+    }
+}
+
+/// Platform fee of a Jupiter route, charged in the output token of exact-in
+/// routes and in the input token of exact-out routes. Routes without a fee
+/// account pass the Jupiter program ID in its place.
+fn jupiter_platform_fee(
+    platform_fee_account: Pubkey,
+    mint: Pubkey,
+    bps: u8,
+    side: FeeSide,
+) -> Option<Commission> {
+    if platform_fee_account == carbon_jupiter_swap_decoder::PROGRAM_ID || bps == 0 {
+        return None;
+    }
+    Some(Commission {
+        recipient: platform_fee_account,
+        mint,
+        bps: bps as u16,
+        side,
+        amount: None,
+    })
+}
+
+/// Highest input an exact-out route accepts: the quote plus slippage.
+fn max_amount_in(quoted_in_amount: u64, slippage_bps: u16) -> u64 {
+    let amount = quoted_in_amount as u128 * (10_000 + slippage_bps as u128) / 10_000;
+    u64::try_from(amount).unwrap_or(u64::MAX)
+}
+
+/// Commission of an OKX commission instruction, charged in the input or the
+/// output mint of `mints` depending on `from_input`. Input-side commissions
+/// are computed up front; output-side ones wait for the executed output.
+fn okx_commission(
+    recipient: Pubkey,
+    mints: (Pubkey, Pubkey),
+    bps: u16,
+    from_input: bool,
+    amount_in: u64,
+) -> Commission {
+    let (mint_in, mint_out) = mints;
+    if from_input {
+        Commission {
+            recipient,
+            mint: mint_in,
+            bps,
+            side: FeeSide::Input,
+            amount: okx_commission_on_net(amount_in, bps),
+        }
+    } else {
+        Commission {
+            recipient,
+            mint: mint_out,
+            bps,
+            side: FeeSide::Output,
+            amount: None,
+        }
+    }
+}
+
+pub fn decode_raydium_instruction(
+    data: Vec<u8>,
+    accounts: Vec<Pubkey>,
+    program_id: Pubkey,
+) -> Result<SwapTransaction, DecodeError> {
+    decode_raydium_instruction_with_metas(data, readonly_metas(&accounts), program_id)
+}
+
+/// Like [`decode_raydium_instruction`], but keeps the signer and writable
+/// flags of the accounts.
+pub fn decode_raydium_instruction_with_metas(
+    data: Vec<u8>,
+    accounts: Vec<AccountMeta>,
+    program_id: Pubkey,
+) -> Result<SwapTransaction, DecodeError> {
+    check_program(carbon_raydium_amm_v4_decoder::PROGRAM_ID, program_id)?;
+
+    let decoder: RaydiumAmmV4Decoder = RaydiumAmmV4Decoder;
 
     let instruction = Instruction {
         program_id,
-        accounts: account_metas, // Adicione as contas necessárias
-        data,                    // Adicione os dados da instrução
+        accounts,
+        data, // Adicione os dados da instrução
     };
 
-    match decoder.decode_instruction(&instruction) {
-        Some(decoded_instruction) => match decoded_instruction.data {
-            RaydiumAmmV4Instruction::SwapBaseIn(ref swap_data) => {
-                let arranged_accounts =
-                    SwapBaseIn::arrange_accounts(&instruction.accounts).unwrap();
-
-                let swap = SwapTransaction {
-                    amm: Some(arranged_accounts.amm),
-                    in_amount: swap_data.amount_in,
-                    out_amount: Some(swap_data.minimum_amount_out),
-                    mint_token_in: None,
-                    mint_token_out: None,
-                    mint_token_account_in: Some(arranged_accounts.user_source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.user_destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            RaydiumAmmV4Instruction::SwapBaseOut(ref swap_data) => {
-                let arranged_accounts =
-                    SwapBaseOut::arrange_accounts(&instruction.accounts).unwrap();
-                let swap = SwapTransaction {
-                    amm: Some(arranged_accounts.amm),
-                    in_amount: swap_data.amount_out,
-                    out_amount: Some(swap_data.amount_out),
-                    mint_token_in: None,
-                    mint_token_out: None,
-                    mint_token_account_in: Some(arranged_accounts.user_source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.user_destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            _ => {
-                return None;
-            }
-        },
-        None => {
-            return None;
+    let decoded_instruction = decoder.decode_instruction(&instruction).ok_or_else(|| {
+        decode_failure(
+            Protocol::RaydiumAmmV4,
+            &instruction.data,
+            &[known::<SwapBaseIn>(), known::<SwapBaseOut>()],
+        )
+    })?;
+
+    match decoded_instruction.data {
+        RaydiumAmmV4Instruction::SwapBaseIn(ref swap_data) => {
+            let arranged_accounts = arrange_accounts::<SwapBaseIn>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                amm: Some(arranged_accounts.amm),
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(swap_data.amount_in),
+                min_amount_out: Some(swap_data.minimum_amount_out),
+                mint_token_account_in: Some(arranged_accounts.user_source_token_account),
+                mint_token_account_out: Some(arranged_accounts.user_destination_token_account),
+                user: Some(arranged_accounts.user_source_owner),
+                ..Default::default()
+            };
+
+            Ok(swap)
         }
+        RaydiumAmmV4Instruction::SwapBaseOut(ref swap_data) => {
+            let arranged_accounts = arrange_accounts::<SwapBaseOut>(&instruction.accounts)?;
+            let swap = SwapTransaction {
+                amm: Some(arranged_accounts.amm),
+                swap_mode: Some(SwapMode::ExactOut),
+                max_amount_in: Some(swap_data.max_amount_in),
+                amount_out: Some(swap_data.amount_out),
+                mint_token_account_in: Some(arranged_accounts.user_source_token_account),
+                mint_token_account_out: Some(arranged_accounts.user_destination_token_account),
+                user: Some(arranged_accounts.user_source_owner),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        other => Err(unsupported_variant(
+            Protocol::RaydiumAmmV4,
+            other.get_instruction_type(),
+        )),
     }
 }
 
@@ -136,99 +364,97 @@ pub fn decode_pumpfun_instruction(
     data: Vec<u8>,
     accounts: Vec<Pubkey>,
     program_id: Pubkey,
-) -> Option<SwapTransaction> {
-    let decoder = PumpfunDecoder;
+) -> Result<SwapTransaction, DecodeError> {
+    decode_pumpfun_instruction_with_metas(data, readonly_metas(&accounts), program_id)
+}
 
-    let account_metas: Vec<AccountMeta> = accounts
-        .iter()
-        .map(|pubkey| AccountMeta {
-            pubkey: *pubkey,
-            is_signer: false,   // Defina como true se a conta for um signatário
-            is_writable: false, // Defina como true se a conta for gravável
-        })
-        .collect();
+/// Like [`decode_pumpfun_instruction`], but keeps the signer and writable
+/// flags of the accounts.
+pub fn decode_pumpfun_instruction_with_metas(
+    data: Vec<u8>,
+    accounts: Vec<AccountMeta>,
+    program_id: Pubkey,
+) -> Result<SwapTransaction, DecodeError> {
+    check_program(carbon_pumpfun_decoder::PROGRAM_ID, program_id)?;
+
+    let decoder = PumpfunDecoder;
 
     let instruction = Instruction {
         program_id,
-        accounts: account_metas, // Adicione as contas necessárias
-        data: data,              // Adicione os dados da instrução
+        accounts,
+        data, // Adicione os dados da instrução
     };
 
-    match decoder.decode_instruction(&instruction) {
-        Some(decoded_instruction) => match decoded_instruction.data {
-            PumpfunInstruction::Create(ref data) => {
-                let arranged_accounts = Create::arrange_accounts(&instruction.accounts).unwrap();
-
-                let swap: SwapTransaction = SwapTransaction {
-                    amm: Some(arranged_accounts.program),
-                    in_amount: 0,
-                    out_amount: None,
-                    mint_token_in: None,
-                    mint_token_out: None,
-                    mint_token_account_in: None,
-                    mint_token_account_out: None,
-                    user: Some(arranged_accounts.user),
-                    mint: Some(arranged_accounts.mint),
-                    create_instruction_accounts: Some(arranged_accounts),
-                };
-
-                return Some(swap);
-            }
-            PumpfunInstruction::Buy(ref data) => {
-                let arranged_accounts =
-                    carbon_pumpfun_decoder::instructions::buy::Buy::arrange_accounts(
-                        &instruction.accounts,
-                    )
-                    .unwrap();
-
-                let swap: SwapTransaction = SwapTransaction {
-                    amm: Some(arranged_accounts.program),
-                    in_amount: data.max_sol_cost,
-                    out_amount: Some(data.amount),
-                    mint_token_in: Some(
-                        Pubkey::from_str("So11111111111111111111111111111111111111112").unwrap(),
-                    ),
-                    mint_token_out: Some(arranged_accounts.mint),
-                    mint_token_account_in: Some(arranged_accounts.associated_user),
-                    mint_token_account_out: Some(arranged_accounts.associated_user),
-                    user: Some(arranged_accounts.user),
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            PumpfunInstruction::Sell(ref data) => {
-                let arranged_accounts =
-                    carbon_pumpfun_decoder::instructions::sell::Sell::arrange_accounts(
-                        &instruction.accounts,
-                    )
-                    .unwrap();
-
-                let swap = SwapTransaction {
-                    amm: Some(arranged_accounts.program),
-                    in_amount: data.amount,
-                    out_amount: Some(data.min_sol_output),
-                    mint_token_in: Some(arranged_accounts.mint),
-                    mint_token_out: Some(
-                        Pubkey::from_str("So11111111111111111111111111111111111111112").unwrap(),
-                    ),
-                    mint_token_account_in: Some(arranged_accounts.associated_user),
-                    mint_token_account_out: Some(arranged_accounts.associated_user),
-                    user: Some(arranged_accounts.user),
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            _ => {
-                return None;
-            }
-        },
-        None => {
-            return None;
+    let decoded_instruction = decoder.decode_instruction(&instruction).ok_or_else(|| {
+        decode_failure(
+            Protocol::Pumpfun,
+            &instruction.data,
+            &[
+                known::<Create>(),
+                known::<carbon_pumpfun_decoder::instructions::buy::Buy>(),
+                known::<carbon_pumpfun_decoder::instructions::sell::Sell>(),
+            ],
+        )
+    })?;
+
+    match decoded_instruction.data {
+        PumpfunInstruction::Create(_) => {
+            let arranged_accounts = arrange_accounts::<Create>(&instruction.accounts)?;
+
+            let swap: SwapTransaction = SwapTransaction {
+                amm: Some(arranged_accounts.program),
+                user: Some(arranged_accounts.user),
+                mint: Some(arranged_accounts.mint),
+                create_instruction_accounts: Some(arranged_accounts),
+                ..Default::default()
+            };
+
+            Ok(swap)
         }
+        PumpfunInstruction::Buy(ref data) => {
+            let arranged_accounts = arrange_accounts::<
+                carbon_pumpfun_decoder::instructions::buy::Buy,
+            >(&instruction.accounts)?;
+
+            let swap: SwapTransaction = SwapTransaction {
+                amm: Some(arranged_accounts.program),
+                swap_mode: Some(SwapMode::ExactOut),
+                max_amount_in: Some(data.max_sol_cost),
+                amount_out: Some(data.amount),
+                mint_token_in: Some(WSOL_MINT),
+                mint_token_out: Some(arranged_accounts.mint),
+                mint_token_account_in: Some(arranged_accounts.user),
+                mint_token_account_out: Some(arranged_accounts.associated_user),
+                user: Some(arranged_accounts.user),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        PumpfunInstruction::Sell(ref data) => {
+            let arranged_accounts = arrange_accounts::<
+                carbon_pumpfun_decoder::instructions::sell::Sell,
+            >(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                amm: Some(arranged_accounts.program),
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(data.amount),
+                min_amount_out: Some(data.min_sol_output),
+                mint_token_in: Some(arranged_accounts.mint),
+                mint_token_out: Some(WSOL_MINT),
+                mint_token_account_in: Some(arranged_accounts.associated_user),
+                mint_token_account_out: Some(arranged_accounts.user),
+                user: Some(arranged_accounts.user),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        other => Err(unsupported_variant(
+            Protocol::Pumpfun,
+            other.get_instruction_type(),
+        )),
     }
 }
 
@@ -236,66 +462,146 @@ pub fn decode_pumpswap_instruction(
     data: Vec<u8>,
     accounts: Vec<Pubkey>,
     program_id: Pubkey,
-) -> Option<SwapTransaction> {
-    let decoder = PumpSwapDecoder;
+) -> Result<SwapTransaction, DecodeError> {
+    decode_pumpswap_instruction_with_metas(data, readonly_metas(&accounts), program_id)
+}
 
-    let account_metas: Vec<AccountMeta> = accounts
-        .iter()
-        .map(|pubkey| AccountMeta {
-            pubkey: *pubkey,
-            is_signer: false,   // Defina como true se a conta for um signatário
-            is_writable: false, // Defina como true se a conta for gravável
-        })
-        .collect();
+/// Like [`decode_pumpswap_instruction`], but keeps the signer and writable
+/// flags of the accounts.
+pub fn decode_pumpswap_instruction_with_metas(
+    data: Vec<u8>,
+    accounts: Vec<AccountMeta>,
+    program_id: Pubkey,
+) -> Result<SwapTransaction, DecodeError> {
+    check_program(carbon_pump_swap_decoder::PROGRAM_ID, program_id)?;
+
+    let decoder = PumpSwapDecoder;
 
     let instruction = Instruction {
         program_id,
-        accounts: account_metas, // Adicione as contas necessárias
-        data: data,              // Adicione os dados da instrução
+        accounts,
+        data, // Adicione os dados da instrução
     };
 
-    match decoder.decode_instruction(&instruction) {
-        Some(decoded_instruction) => match decoded_instruction.data {
-            // PumpSwapInstruction::Buy(ref data) => {
-            //     let arranged_accounts = Buy::arrange_accounts(&instruction.accounts).unwrap();
-
-            //     let swap: SwapTransaction = SwapTransaction {
-            //         amm: Some(arranged_accounts.program),
-            //         in_amount: data.base_amount_out,
-            //         out_amount: Some(data.max_quote_amount_in),
-            //         mint_token_in: Some(arranged_accounts.base_mint),
-            //         mint_token_out: Some(arranged_accounts.quote_mint),
-            //         mint_token_account_in: Some(arranged_accounts.user_base_token_account),
-            //         mint_token_account_out: Some(arranged_accounts.user_quote_token_account),
-            //     };
-
-            //     return Some(swap);
-            // }
-            PumpSwapInstruction::Sell(ref data) => {
-                let arranged_accounts = Sell::arrange_accounts(&instruction.accounts).unwrap();
-
-                let swap = SwapTransaction {
-                    amm: Some(arranged_accounts.program),
-                    in_amount: data.base_amount_in,
-                    out_amount: Some(data.min_quote_amount_out),
-                    mint_token_in: Some(arranged_accounts.base_mint),
-                    mint_token_out: Some(arranged_accounts.quote_mint),
-                    mint_token_account_in: Some(arranged_accounts.user_base_token_account),
-                    mint_token_account_out: Some(arranged_accounts.user_quote_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            _ => {
-                return None;
-            }
-        },
-        None => {
-            return None;
+    let decoded_instruction = decoder.decode_instruction(&instruction).ok_or_else(|| {
+        decode_failure(
+            Protocol::PumpSwap,
+            &instruction.data,
+            &[
+                known::<Buy>(),
+                known::<Sell>(),
+                known::<Deposit>(),
+                known::<Withdraw>(),
+                known::<CreatePool>(),
+            ],
+        )
+    })?;
+
+    match decoded_instruction.data {
+        PumpSwapInstruction::Buy(ref data) => {
+            let arranged_accounts = arrange_accounts::<Buy>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                amm: Some(arranged_accounts.program),
+                swap_mode: Some(SwapMode::ExactOut),
+                max_amount_in: Some(data.max_quote_amount_in),
+                amount_out: Some(data.base_amount_out),
+                mint_token_in: Some(arranged_accounts.quote_mint),
+                mint_token_out: Some(arranged_accounts.base_mint),
+                mint_token_account_in: Some(arranged_accounts.user_quote_token_account),
+                mint_token_account_out: Some(arranged_accounts.user_base_token_account),
+                user: Some(arranged_accounts.user),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        PumpSwapInstruction::Sell(ref data) => {
+            let arranged_accounts = arrange_accounts::<Sell>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                amm: Some(arranged_accounts.program),
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(data.base_amount_in),
+                min_amount_out: Some(data.min_quote_amount_out),
+                mint_token_in: Some(arranged_accounts.base_mint),
+                mint_token_out: Some(arranged_accounts.quote_mint),
+                mint_token_account_in: Some(arranged_accounts.user_base_token_account),
+                mint_token_account_out: Some(arranged_accounts.user_quote_token_account),
+                user: Some(arranged_accounts.user),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        PumpSwapInstruction::Deposit(ref data) => {
+            let arranged_accounts = arrange_accounts::<Deposit>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                amm: Some(arranged_accounts.program),
+                user: Some(arranged_accounts.user),
+                liquidity: Some(LiquidityEvent {
+                    action: LiquidityAction::Deposit,
+                    pool: arranged_accounts.pool,
+                    base_mint: arranged_accounts.base_mint,
+                    quote_mint: arranged_accounts.quote_mint,
+                    lp_mint: arranged_accounts.lp_mint,
+                    lp_amount: Some(data.lp_token_amount_out),
+                    base_amount: data.max_base_amount_in,
+                    quote_amount: data.max_quote_amount_in,
+                }),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        PumpSwapInstruction::Withdraw(ref data) => {
+            let arranged_accounts = arrange_accounts::<Withdraw>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                amm: Some(arranged_accounts.program),
+                user: Some(arranged_accounts.user),
+                liquidity: Some(LiquidityEvent {
+                    action: LiquidityAction::Withdraw,
+                    pool: arranged_accounts.pool,
+                    base_mint: arranged_accounts.base_mint,
+                    quote_mint: arranged_accounts.quote_mint,
+                    lp_mint: arranged_accounts.lp_mint,
+                    lp_amount: Some(data.lp_token_amount_in),
+                    base_amount: data.min_base_amount_out,
+                    quote_amount: data.min_quote_amount_out,
+                }),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        PumpSwapInstruction::CreatePool(ref data) => {
+            let arranged_accounts = arrange_accounts::<CreatePool>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                amm: Some(arranged_accounts.program),
+                user: Some(arranged_accounts.creator),
+                mint: Some(arranged_accounts.base_mint),
+                liquidity: Some(LiquidityEvent {
+                    action: LiquidityAction::CreatePool,
+                    pool: arranged_accounts.pool,
+                    base_mint: arranged_accounts.base_mint,
+                    quote_mint: arranged_accounts.quote_mint,
+                    lp_mint: arranged_accounts.lp_mint,
+                    lp_amount: None,
+                    base_amount: data.base_amount_in,
+                    quote_amount: data.quote_amount_in,
+                }),
+                ..Default::default()
+            };
+
+            Ok(swap)
         }
+        other => Err(unsupported_variant(
+            Protocol::PumpSwap,
+            other.get_instruction_type(),
+        )),
     }
 }
 
@@ -303,122 +609,216 @@ pub fn decode_jupiter_instruction(
     data: Vec<u8>,
     accounts: Vec<Pubkey>,
     program_id: Pubkey,
-) -> Option<SwapTransaction> {
-    let decoder = JupiterSwapDecoder;
+) -> Result<SwapTransaction, DecodeError> {
+    decode_jupiter_instruction_with_metas(data, readonly_metas(&accounts), program_id)
+}
 
-    let account_metas: Vec<AccountMeta> = accounts
-        .iter()
-        .map(|pubkey| AccountMeta {
-            pubkey: *pubkey,
-            is_signer: false,   // Defina como true se a conta for um signatário
-            is_writable: false, // Defina como true se a conta for gravável
-        })
-        .collect();
+/// Like [`decode_jupiter_instruction`], but keeps the signer and writable
+/// flags of the accounts.
+pub fn decode_jupiter_instruction_with_metas(
+    data: Vec<u8>,
+    accounts: Vec<AccountMeta>,
+    program_id: Pubkey,
+) -> Result<SwapTransaction, DecodeError> {
+    check_program(carbon_jupiter_swap_decoder::PROGRAM_ID, program_id)?;
+
+    let decoder = JupiterSwapDecoder;
 
     let instruction = Instruction {
         program_id,
-        accounts: account_metas, // Adicione as contas necessárias
-        data: data,              // Adicione os dados da instrução
+        accounts,
+        data, // Adicione os dados da instrução
     };
 
-    match decoder.decode_instruction(&instruction) {
-        Some(decoded_instruction) => match decoded_instruction.data {
-            JupiterSwapInstruction::Route(ref data) => {
-                let arranged_accounts = Route::arrange_accounts(&instruction.accounts).unwrap();
-
-                let swap = SwapTransaction {
-                    amm: None,
-                    in_amount: data.in_amount,
-                    out_amount: Some(data.quoted_out_amount),
-                    mint_token_in: None,
-                    mint_token_out: Some(arranged_accounts.destination_mint),
-                    mint_token_account_in: Some(arranged_accounts.user_source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.user_destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            JupiterSwapInstruction::SharedAccountsExactOutRoute(ref data) => {
-                let arranged_accounts =
-                    SharedAccountsExactOutRoute::arrange_accounts(&instruction.accounts).unwrap();
-
-                let swap = SwapTransaction {
-                    amm: None,
-                    in_amount: data.out_amount,
-                    out_amount: Some(data.out_amount),
-                    mint_token_in: Some(arranged_accounts.source_mint),
-                    mint_token_out: Some(arranged_accounts.destination_mint),
-                    mint_token_account_in: Some(arranged_accounts.source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            JupiterSwapInstruction::SharedAccountsRoute(ref data) => {
-                let arranged_accounts =
-                    SharedAccountsRoute::arrange_accounts(&instruction.accounts).unwrap();
-                let swap = SwapTransaction {
-                    amm: None,
-                    in_amount: data.in_amount,
-                    out_amount: Some(data.quoted_out_amount),
-                    mint_token_in: Some(arranged_accounts.source_mint),
-                    mint_token_out: Some(arranged_accounts.destination_mint),
-                    mint_token_account_in: Some(arranged_accounts.source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            JupiterSwapInstruction::SwapEvent(ref data) => {
-                let swap = SwapTransaction {
-                    amm: None,
-                    in_amount: data.input_amount,
-                    out_amount: Some(data.output_amount),
-                    mint_token_in: Some(data.input_mint),
-                    mint_token_out: Some(data.output_mint),
-                    mint_token_account_in: None,
-                    mint_token_account_out: None,
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-                return Some(swap);
-            }
-            JupiterSwapInstruction::ExactOutRoute(ref data) => {
-                let arranged_accounts =
-                    ExactOutRoute::arrange_accounts(&instruction.accounts).unwrap();
-
-                let swap = SwapTransaction {
-                    amm: None,
-                    in_amount: data.quoted_in_amount,
-                    out_amount: Some(data.out_amount),
-                    mint_token_in: Some(arranged_accounts.source_mint),
-                    mint_token_out: Some(arranged_accounts.user_destination_token_account),
-                    mint_token_account_in: Some(arranged_accounts.user_source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            _ => {
-                return None;
-            }
-        },
-        None => {
-            return None;
+    let decoded_instruction = decoder.decode_instruction(&instruction).ok_or_else(|| {
+        decode_failure(
+            Protocol::Jupiter,
+            &instruction.data,
+            &[
+                known::<Route>(),
+                known::<SharedAccountsExactOutRoute>(),
+                known::<SharedAccountsRoute>(),
+                known::<ExactOutRoute>(),
+                known::<RouteWithTokenLedger>(),
+                known::<SharedAccountsRouteWithTokenLedger>(),
+            ],
+        )
+    })?;
+
+    match decoded_instruction.data {
+        JupiterSwapInstruction::Route(ref data) => {
+            let arranged_accounts = arrange_accounts::<Route>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(data.in_amount),
+                min_amount_out: Some(min_amount_out(data.quoted_out_amount, data.slippage_bps)),
+                quoted_amount_out: Some(data.quoted_out_amount),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.user_source_token_account),
+                mint_token_account_out: Some(jupiter_destination(
+                    arranged_accounts.user_destination_token_account,
+                    arranged_accounts.destination_token_account,
+                )),
+                user: Some(arranged_accounts.user_transfer_authority),
+                jupiter_hops: jupiter_hops(&data.route_plan),
+                commission: jupiter_platform_fee(
+                    arranged_accounts.platform_fee_account,
+                    arranged_accounts.destination_mint,
+                    data.platform_fee_bps,
+                    FeeSide::Output,
+                ),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        JupiterSwapInstruction::SharedAccountsExactOutRoute(ref data) => {
+            let arranged_accounts =
+                arrange_accounts::<SharedAccountsExactOutRoute>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactOut),
+                max_amount_in: Some(max_amount_in(data.quoted_in_amount, data.slippage_bps)),
+                amount_out: Some(data.out_amount),
+                quoted_amount_in: Some(data.quoted_in_amount),
+                mint_token_in: Some(arranged_accounts.source_mint),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.source_token_account),
+                mint_token_account_out: Some(arranged_accounts.destination_token_account),
+                user: Some(arranged_accounts.user_transfer_authority),
+                jupiter_hops: jupiter_hops(&data.route_plan),
+                commission: jupiter_platform_fee(
+                    arranged_accounts.platform_fee_account,
+                    arranged_accounts.source_mint,
+                    data.platform_fee_bps,
+                    FeeSide::Input,
+                ),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        JupiterSwapInstruction::SharedAccountsRoute(ref data) => {
+            let arranged_accounts = arrange_accounts::<SharedAccountsRoute>(&instruction.accounts)?;
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(data.in_amount),
+                min_amount_out: Some(min_amount_out(data.quoted_out_amount, data.slippage_bps)),
+                quoted_amount_out: Some(data.quoted_out_amount),
+                mint_token_in: Some(arranged_accounts.source_mint),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.source_token_account),
+                mint_token_account_out: Some(arranged_accounts.destination_token_account),
+                user: Some(arranged_accounts.user_transfer_authority),
+                jupiter_hops: jupiter_hops(&data.route_plan),
+                commission: jupiter_platform_fee(
+                    arranged_accounts.platform_fee_account,
+                    arranged_accounts.destination_mint,
+                    data.platform_fee_bps,
+                    FeeSide::Output,
+                ),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        JupiterSwapInstruction::RouteWithTokenLedger(ref data) => {
+            let arranged_accounts =
+                arrange_accounts::<RouteWithTokenLedger>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactIn),
+                min_amount_out: Some(min_amount_out(data.quoted_out_amount, data.slippage_bps)),
+                quoted_amount_out: Some(data.quoted_out_amount),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.user_source_token_account),
+                mint_token_account_out: Some(jupiter_destination(
+                    arranged_accounts.user_destination_token_account,
+                    arranged_accounts.destination_token_account,
+                )),
+                user: Some(arranged_accounts.user_transfer_authority),
+                jupiter_hops: jupiter_hops(&data.route_plan),
+                commission: jupiter_platform_fee(
+                    arranged_accounts.platform_fee_account,
+                    arranged_accounts.destination_mint,
+                    data.platform_fee_bps,
+                    FeeSide::Output,
+                ),
+                token_ledger: Some(arranged_accounts.token_ledger),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        JupiterSwapInstruction::SharedAccountsRouteWithTokenLedger(ref data) => {
+            let arranged_accounts =
+                arrange_accounts::<SharedAccountsRouteWithTokenLedger>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactIn),
+                min_amount_out: Some(min_amount_out(data.quoted_out_amount, data.slippage_bps)),
+                quoted_amount_out: Some(data.quoted_out_amount),
+                mint_token_in: Some(arranged_accounts.source_mint),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.source_token_account),
+                mint_token_account_out: Some(arranged_accounts.destination_token_account),
+                user: Some(arranged_accounts.user_transfer_authority),
+                jupiter_hops: jupiter_hops(&data.route_plan),
+                commission: jupiter_platform_fee(
+                    arranged_accounts.platform_fee_account,
+                    arranged_accounts.destination_mint,
+                    data.platform_fee_bps,
+                    FeeSide::Output,
+                ),
+                token_ledger: Some(arranged_accounts.token_ledger),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        JupiterSwapInstruction::SwapEvent(ref data) => {
+            let swap = SwapTransaction {
+                amount_in: Some(data.input_amount),
+                amount_out: Some(data.output_amount),
+                mint_token_in: Some(data.input_mint),
+                mint_token_out: Some(data.output_mint),
+                ..Default::default()
+            };
+            Ok(swap)
         }
+        JupiterSwapInstruction::ExactOutRoute(ref data) => {
+            let arranged_accounts = arrange_accounts::<ExactOutRoute>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactOut),
+                max_amount_in: Some(max_amount_in(data.quoted_in_amount, data.slippage_bps)),
+                amount_out: Some(data.out_amount),
+                quoted_amount_in: Some(data.quoted_in_amount),
+                mint_token_in: Some(arranged_accounts.source_mint),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.user_source_token_account),
+                mint_token_account_out: Some(jupiter_destination(
+                    arranged_accounts.user_destination_token_account,
+                    arranged_accounts.destination_token_account,
+                )),
+                user: Some(arranged_accounts.user_transfer_authority),
+                jupiter_hops: jupiter_hops(&data.route_plan),
+                commission: jupiter_platform_fee(
+                    arranged_accounts.platform_fee_account,
+                    arranged_accounts.source_mint,
+                    data.platform_fee_bps,
+                    FeeSide::Input,
+                ),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        other => Err(unsupported_variant(
+            Protocol::Jupiter,
+            other.get_instruction_type(),
+        )),
     }
 }
 
@@ -426,343 +826,607 @@ pub fn decode_okx_instruction(
     okx_data: Vec<u8>,
     accounts: Vec<Pubkey>,
     program_id: Pubkey,
-) -> Option<SwapTransaction> {
-    let decoder = OkxDexDecoder;
+) -> Result<SwapTransaction, DecodeError> {
+    decode_okx_instruction_with_metas(okx_data, readonly_metas(&accounts), program_id)
+}
 
-    let account_metas: Vec<AccountMeta> = accounts
-        .iter()
-        .map(|pubkey| AccountMeta {
-            pubkey: *pubkey,
-            is_signer: false,   // Defina como true se a conta for um signatário
-            is_writable: false, // Defina como true se a conta for gravável
-        })
-        .collect();
+/// Like [`decode_okx_instruction`], but keeps the signer and writable flags
+/// of the accounts.
+pub fn decode_okx_instruction_with_metas(
+    okx_data: Vec<u8>,
+    accounts: Vec<AccountMeta>,
+    program_id: Pubkey,
+) -> Result<SwapTransaction, DecodeError> {
+    decode_okx_instruction_in(okx_data, accounts, program_id, &OkxPrograms::default())
+}
+
+/// Like [`decode_okx_instruction_with_metas`], accepting the router deployments in
+/// `okx_programs` instead of just the mainnet one.
+pub fn decode_okx_instruction_in(
+    okx_data: Vec<u8>,
+    accounts: Vec<AccountMeta>,
+    program_id: Pubkey,
+    okx_programs: &OkxPrograms,
+) -> Result<SwapTransaction, DecodeError> {
+    decode_okx_swap(okx_data, accounts, program_id, okx_programs).map(|(swap, _)| swap)
+}
+
+fn decode_okx_swap(
+    okx_data: Vec<u8>,
+    accounts: Vec<AccountMeta>,
+    program_id: Pubkey,
+    okx_programs: &OkxPrograms,
+) -> Result<(SwapTransaction, Option<SwapEvent>), DecodeError> {
+    if !okx_programs.contains(&program_id) {
+        return Err(DecodeError::UnsupportedProgram(program_id));
+    }
+
+    let decoder = OkxDexDecoder {
+        programs: okx_programs.clone(),
+    };
 
     let instruction = Instruction {
         program_id,
-        accounts: account_metas, // Adicione as contas necessárias
-        data: okx_data,          // Adicione os dados da instrução
+        accounts,
+        data: okx_data, // Adicione os dados da instrução
     };
 
-    match decoder.decode_instruction(&instruction) {
-        Some(decoded_instruction) => match decoded_instruction.data {
-            OkxDexInstruction::Swap(ref swap_data) => {
-                let arranged_accounts = Swap::arrange_accounts(&instruction.accounts).unwrap();
-
-                let swap = SwapTransaction {
-                    amm: None,
-                    in_amount: swap_data.data.amount_in,
-                    out_amount: Some(swap_data.data.expect_amount_out),
-                    mint_token_in: Some(arranged_accounts.source_mint),
-                    mint_token_out: Some(arranged_accounts.destination_mint),
-                    mint_token_account_in: Some(arranged_accounts.source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            OkxDexInstruction::Swap2(ref swap_data) => {
-                let arranged_accounts = Swap2::arrange_accounts(&instruction.accounts).unwrap();
-
-                let swap = SwapTransaction {
-                    amm: None,
-                    in_amount: swap_data.data.amount_in,
-                    out_amount: Some(swap_data.data.expect_amount_out),
-                    mint_token_in: Some(arranged_accounts.source_mint),
-                    mint_token_out: Some(arranged_accounts.destination_mint),
-                    mint_token_account_in: Some(arranged_accounts.source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            OkxDexInstruction::CommissionSplSwap(ref swap_data) => {
-                let arranged_accounts =
-                    CommissionSplSwap::arrange_accounts(&instruction.accounts).unwrap();
-
-                let swap = SwapTransaction {
-                    amm: None,
-                    in_amount: swap_data.data.amount_in,
-                    out_amount: Some(swap_data.data.expect_amount_out),
-                    mint_token_in: Some(arranged_accounts.source_mint),
-                    mint_token_out: Some(arranged_accounts.destination_mint),
-                    mint_token_account_in: Some(arranged_accounts.source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            OkxDexInstruction::CommissionSplSwap2(ref swap_data) => {
-                let arranged_accounts =
-                    CommissionSplSwap2::arrange_accounts(&instruction.accounts).unwrap();
-
-                let swap = SwapTransaction {
-                    amm: None,
-                    in_amount: swap_data.data.amount_in,
-                    out_amount: Some(swap_data.data.expect_amount_out),
-                    mint_token_in: Some(arranged_accounts.source_mint),
-                    mint_token_out: Some(arranged_accounts.destination_mint),
-                    mint_token_account_in: Some(arranged_accounts.source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            OkxDexInstruction::CommissionSplFromSwap(ref swap_data) => {
-                let arranged_accounts =
-                    commission_spl_from_swap::CommissionSplFromSwap::arrange_accounts(
-                        &instruction.accounts,
-                    )
-                    .unwrap();
-
-                let swap = SwapTransaction {
-                    amm: None,
-                    in_amount: swap_data.args.amount_in,
-                    out_amount: Some(swap_data.args.expect_amount_out),
-                    mint_token_in: Some(arranged_accounts.source_mint),
-                    mint_token_out: Some(arranged_accounts.destination_mint),
-                    mint_token_account_in: Some(arranged_accounts.source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            OkxDexInstruction::CommissionSplProxySwap(ref swap_data) => {
-                let arranged_accounts =
-                    commission_spl_proxy_swap::CommissionSplProxySwap::arrange_accounts(
-                        &instruction.accounts,
-                    )
-                    .unwrap();
-
-                let swap = SwapTransaction {
-                    amm: None,
-                    in_amount: swap_data.data.amount_in,
-                    out_amount: Some(swap_data.data.expect_amount_out),
-                    mint_token_in: Some(arranged_accounts.source_mint),
-                    mint_token_out: Some(arranged_accounts.destination_mint),
-                    mint_token_account_in: Some(arranged_accounts.source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            OkxDexInstruction::CommissionSolFromSwap(ref swap_data) => {
-                let arranged_accounts =
-                    commission_sol_from_swap::CommissionSolFromSwap::arrange_accounts(
-                        &instruction.accounts,
-                    )
-                    .unwrap();
-
-                let swap = SwapTransaction {
-                    amm: None,
-                    in_amount: swap_data.args.amount_in,
-                    out_amount: Some(swap_data.args.expect_amount_out),
-                    mint_token_in: Some(arranged_accounts.source_mint),
-                    mint_token_out: Some(arranged_accounts.destination_mint),
-                    mint_token_account_in: Some(arranged_accounts.source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            OkxDexInstruction::CommissionSolProxySwap(ref swap_data) => {
-                let arranged_accounts =
-                    commission_sol_proxy_swap::CommissionSolProxySwap::arrange_accounts(
-                        &instruction.accounts,
-                    )
-                    .unwrap();
-
-                let swap = SwapTransaction {
-                    amm: None,
-                    in_amount: swap_data.data.amount_in,
-                    out_amount: Some(swap_data.data.expect_amount_out),
-                    mint_token_in: Some(arranged_accounts.source_mint),
-                    mint_token_out: Some(arranged_accounts.destination_mint),
-                    mint_token_account_in: Some(arranged_accounts.source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            OkxDexInstruction::CommissionSolSwap(ref swap_data) => {
-                let arranged_accounts =
-                    commission_sol_swap::CommissionSolSwap::arrange_accounts(&instruction.accounts)
-                        .unwrap();
-
-                let swap = SwapTransaction {
-                    amm: None,
-                    in_amount: swap_data.data.amount_in,
-                    out_amount: Some(swap_data.data.expect_amount_out),
-                    mint_token_in: Some(arranged_accounts.source_mint),
-                    mint_token_out: Some(arranged_accounts.destination_mint),
-                    mint_token_account_in: Some(arranged_accounts.source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            OkxDexInstruction::CommissionSolSwap2(ref swap_data) => {
-                let arranged_accounts = commission_sol_swap2::CommissionSolSwap2::arrange_accounts(
-                    &instruction.accounts,
-                )
-                .unwrap();
-
-                let swap = SwapTransaction {
-                    amm: None,
-                    in_amount: swap_data.data.amount_in,
-                    out_amount: Some(swap_data.data.expect_amount_out),
-                    mint_token_in: Some(arranged_accounts.source_mint),
-                    mint_token_out: Some(arranged_accounts.destination_mint),
-                    mint_token_account_in: Some(arranged_accounts.source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            OkxDexInstruction::FromSwapLog(ref swap_data) => {
-                let arranged_accounts =
-                    from_swap_log::FromSwapLog::arrange_accounts(&instruction.accounts).unwrap();
-
-                let swap = SwapTransaction {
-                    amm: None,
-                    in_amount: swap_data.args.amount_in,
-                    out_amount: Some(swap_data.args.expect_amount_out),
-                    mint_token_in: Some(arranged_accounts.source_mint),
-                    mint_token_out: Some(arranged_accounts.destination_mint),
-                    mint_token_account_in: Some(arranged_accounts.source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            OkxDexInstruction::ProxySwap(ref swap_data) => {
-                let arranged_accounts =
-                    proxy_swap::ProxySwap::arrange_accounts(&instruction.accounts).unwrap();
-
-                let swap = SwapTransaction {
-                    amm: None,
-                    in_amount: swap_data.data.amount_in,
-                    out_amount: Some(swap_data.data.expect_amount_out),
-                    mint_token_in: Some(arranged_accounts.source_mint),
-                    mint_token_out: Some(arranged_accounts.destination_mint),
-                    mint_token_account_in: Some(arranged_accounts.source_token_account),
-                    mint_token_account_out: Some(arranged_accounts.destination_token_account),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            _ => {
-                return None;
-            }
-        },
-        None => {
-            return None;
+    let decoded_instruction = decoder.decode_instruction(&instruction).ok_or_else(|| {
+        decode_failure(
+            Protocol::Okx,
+            &instruction.data,
+            &[
+                known::<Swap>(),
+                known::<Swap2>(),
+                known::<CommissionSplSwap>(),
+                known::<CommissionSplSwap2>(),
+                known::<commission_spl_from_swap::CommissionSplFromSwap>(),
+                known::<commission_spl_proxy_swap::CommissionSplProxySwap>(),
+                known::<commission_sol_from_swap::CommissionSolFromSwap>(),
+                known::<commission_sol_proxy_swap::CommissionSolProxySwap>(),
+                known::<commission_sol_swap::CommissionSolSwap>(),
+                known::<commission_sol_swap2::CommissionSolSwap2>(),
+                known::<from_swap_log::FromSwapLog>(),
+                known::<proxy_swap::ProxySwap>(),
+                known::<swap_event::SwapEvent>(),
+            ],
+        )
+    })?;
+
+    let legs = decoded_instruction.data.legs().unwrap_or_default();
+    let route_violations = decoded_instruction.data.route_violations();
+    let event = match &decoded_instruction.data {
+        OkxDexInstruction::SwapEvent(event) => Some(SwapEvent {
+            dex: event.dex.clone(),
+            amount_in: event.amount_in,
+            amount_out: event.amount_out,
+        }),
+        _ => None,
+    };
+
+    let swap = match decoded_instruction.data {
+        OkxDexInstruction::Swap(ref swap_data) => {
+            let arranged_accounts = arrange_accounts::<Swap>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(swap_data.data.amount_in),
+                min_amount_out: Some(swap_data.data.min_return),
+                quoted_amount_out: Some(swap_data.data.expect_amount_out),
+                mint_token_in: Some(arranged_accounts.source_mint),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.source_token_account),
+                mint_token_account_out: Some(arranged_accounts.destination_token_account),
+                user: Some(arranged_accounts.payer),
+                okx_legs: legs,
+                route_violations,
+                ..Default::default()
+            };
+
+            Ok(swap)
         }
-    }
+        OkxDexInstruction::Swap2(ref swap_data) => {
+            let arranged_accounts = arrange_accounts::<Swap2>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(swap_data.data.amount_in),
+                min_amount_out: Some(swap_data.data.min_return),
+                quoted_amount_out: Some(swap_data.data.expect_amount_out),
+                mint_token_in: Some(arranged_accounts.source_mint),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.source_token_account),
+                mint_token_account_out: Some(arranged_accounts.destination_token_account),
+                user: Some(arranged_accounts.payer),
+                okx_legs: legs,
+                route_violations,
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        OkxDexInstruction::CommissionSplSwap(ref swap_data) => {
+            let arranged_accounts = arrange_accounts::<CommissionSplSwap>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(swap_data.data.amount_in),
+                min_amount_out: Some(swap_data.data.min_return),
+                quoted_amount_out: Some(swap_data.data.expect_amount_out),
+                mint_token_in: Some(arranged_accounts.source_mint),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.source_token_account),
+                mint_token_account_out: Some(arranged_accounts.destination_token_account),
+                user: Some(arranged_accounts.payer),
+                okx_legs: legs,
+                route_violations,
+                commission: Some(okx_commission(
+                    arranged_accounts.commission_token_account,
+                    (
+                        arranged_accounts.source_mint,
+                        arranged_accounts.destination_mint,
+                    ),
+                    swap_data.data.commission_rate,
+                    swap_data.data.commission_direction,
+                    swap_data.data.amount_in,
+                )),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        OkxDexInstruction::CommissionSplSwap2(ref swap_data) => {
+            let arranged_accounts = arrange_accounts::<CommissionSplSwap2>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(swap_data.data.amount_in),
+                min_amount_out: Some(swap_data.data.min_return),
+                quoted_amount_out: Some(swap_data.data.expect_amount_out),
+                mint_token_in: Some(arranged_accounts.source_mint),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.source_token_account),
+                mint_token_account_out: Some(arranged_accounts.destination_token_account),
+                user: Some(arranged_accounts.payer),
+                okx_legs: legs,
+                route_violations,
+                commission: Some(okx_commission(
+                    arranged_accounts.commission_token_account,
+                    (
+                        arranged_accounts.source_mint,
+                        arranged_accounts.destination_mint,
+                    ),
+                    swap_data.data.commission_rate,
+                    swap_data.data.commission_direction,
+                    swap_data.data.amount_in,
+                )),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        OkxDexInstruction::CommissionSplFromSwap(ref swap_data) => {
+            let arranged_accounts = arrange_accounts::<
+                commission_spl_from_swap::CommissionSplFromSwap,
+            >(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(swap_data.args.amount_in),
+                min_amount_out: Some(swap_data.args.min_return),
+                quoted_amount_out: Some(swap_data.args.expect_amount_out),
+                mint_token_in: Some(arranged_accounts.source_mint),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.source_token_account),
+                mint_token_account_out: Some(arranged_accounts.destination_token_account),
+                user: Some(arranged_accounts.payer),
+                okx_legs: legs,
+                route_violations,
+                commission: Some(okx_commission(
+                    arranged_accounts.commission_token_account,
+                    (
+                        arranged_accounts.source_mint,
+                        arranged_accounts.destination_mint,
+                    ),
+                    swap_data.commission_rate,
+                    true,
+                    swap_data.args.amount_in,
+                )),
+                bridge: Some(BridgeTransfer::from(&swap_data.bridge_to_args)),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        OkxDexInstruction::CommissionSplProxySwap(ref swap_data) => {
+            let arranged_accounts = arrange_accounts::<
+                commission_spl_proxy_swap::CommissionSplProxySwap,
+            >(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(swap_data.data.amount_in),
+                min_amount_out: Some(swap_data.data.min_return),
+                quoted_amount_out: Some(swap_data.data.expect_amount_out),
+                mint_token_in: Some(arranged_accounts.source_mint),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.source_token_account),
+                mint_token_account_out: Some(arranged_accounts.destination_token_account),
+                user: Some(arranged_accounts.payer),
+                okx_legs: legs,
+                route_violations,
+                commission: Some(okx_commission(
+                    arranged_accounts.commission_token_account,
+                    (
+                        arranged_accounts.source_mint,
+                        arranged_accounts.destination_mint,
+                    ),
+                    swap_data.commission_rate,
+                    swap_data.commission_direction,
+                    swap_data.data.amount_in,
+                )),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        OkxDexInstruction::CommissionSolFromSwap(ref swap_data) => {
+            let arranged_accounts = arrange_accounts::<
+                commission_sol_from_swap::CommissionSolFromSwap,
+            >(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(swap_data.args.amount_in),
+                min_amount_out: Some(swap_data.args.min_return),
+                quoted_amount_out: Some(swap_data.args.expect_amount_out),
+                mint_token_in: Some(arranged_accounts.source_mint),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.source_token_account),
+                mint_token_account_out: Some(arranged_accounts.destination_token_account),
+                user: Some(arranged_accounts.payer),
+                okx_legs: legs,
+                route_violations,
+                commission: Some(okx_commission(
+                    arranged_accounts.commission_account,
+                    (WSOL_MINT, WSOL_MINT),
+                    swap_data.commission_rate,
+                    true,
+                    swap_data.args.amount_in,
+                )),
+                bridge: Some(BridgeTransfer::from(&swap_data.bridge_to_args)),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        OkxDexInstruction::CommissionSolProxySwap(ref swap_data) => {
+            let arranged_accounts = arrange_accounts::<
+                commission_sol_proxy_swap::CommissionSolProxySwap,
+            >(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(swap_data.data.amount_in),
+                min_amount_out: Some(swap_data.data.min_return),
+                quoted_amount_out: Some(swap_data.data.expect_amount_out),
+                mint_token_in: Some(arranged_accounts.source_mint),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.source_token_account),
+                mint_token_account_out: Some(arranged_accounts.destination_token_account),
+                user: Some(arranged_accounts.payer),
+                okx_legs: legs,
+                route_violations,
+                commission: Some(okx_commission(
+                    arranged_accounts.commission_account,
+                    (WSOL_MINT, WSOL_MINT),
+                    swap_data.commission_rate,
+                    swap_data.commission_direction,
+                    swap_data.data.amount_in,
+                )),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        OkxDexInstruction::CommissionSolSwap(ref swap_data) => {
+            let arranged_accounts =
+                arrange_accounts::<commission_sol_swap::CommissionSolSwap>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(swap_data.data.amount_in),
+                min_amount_out: Some(swap_data.data.min_return),
+                quoted_amount_out: Some(swap_data.data.expect_amount_out),
+                mint_token_in: Some(arranged_accounts.source_mint),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.source_token_account),
+                mint_token_account_out: Some(arranged_accounts.destination_token_account),
+                user: Some(arranged_accounts.payer),
+                okx_legs: legs,
+                route_violations,
+                commission: Some(okx_commission(
+                    arranged_accounts.commission_account,
+                    (WSOL_MINT, WSOL_MINT),
+                    swap_data.data.commission_rate,
+                    swap_data.data.commission_direction,
+                    swap_data.data.amount_in,
+                )),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        OkxDexInstruction::CommissionSolSwap2(ref swap_data) => {
+            let arranged_accounts = arrange_accounts::<commission_sol_swap2::CommissionSolSwap2>(
+                &instruction.accounts,
+            )?;
+
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(swap_data.data.amount_in),
+                min_amount_out: Some(swap_data.data.min_return),
+                quoted_amount_out: Some(swap_data.data.expect_amount_out),
+                mint_token_in: Some(arranged_accounts.source_mint),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.source_token_account),
+                mint_token_account_out: Some(arranged_accounts.destination_token_account),
+                user: Some(arranged_accounts.payer),
+                okx_legs: legs,
+                route_violations,
+                commission: Some(okx_commission(
+                    arranged_accounts.commission_account,
+                    (WSOL_MINT, WSOL_MINT),
+                    swap_data.data.commission_rate,
+                    swap_data.data.commission_direction,
+                    swap_data.data.amount_in,
+                )),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        OkxDexInstruction::FromSwapLog(ref swap_data) => {
+            let arranged_accounts =
+                arrange_accounts::<from_swap_log::FromSwapLog>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(swap_data.args.amount_in),
+                min_amount_out: Some(swap_data.args.min_return),
+                quoted_amount_out: Some(swap_data.args.expect_amount_out),
+                mint_token_in: Some(arranged_accounts.source_mint),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.source_token_account),
+                mint_token_account_out: Some(arranged_accounts.destination_token_account),
+                user: Some(arranged_accounts.payer),
+                okx_legs: legs,
+                route_violations,
+                bridge: Some(BridgeTransfer::from(&swap_data.bridge_to_args)),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        OkxDexInstruction::ProxySwap(ref swap_data) => {
+            let arranged_accounts =
+                arrange_accounts::<proxy_swap::ProxySwap>(&instruction.accounts)?;
+
+            let swap = SwapTransaction {
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(swap_data.data.amount_in),
+                min_amount_out: Some(swap_data.data.min_return),
+                quoted_amount_out: Some(swap_data.data.expect_amount_out),
+                mint_token_in: Some(arranged_accounts.source_mint),
+                mint_token_out: Some(arranged_accounts.destination_mint),
+                mint_token_account_in: Some(arranged_accounts.source_token_account),
+                mint_token_account_out: Some(arranged_accounts.destination_token_account),
+                user: Some(arranged_accounts.payer),
+                okx_legs: legs,
+                route_violations,
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        OkxDexInstruction::SwapEvent(ref event) => {
+            let swap = SwapTransaction {
+                amount_in: Some(event.amount_in),
+                amount_out: Some(event.amount_out),
+                okx_legs: vec![SwapLeg {
+                    route: 0,
+                    hop: 0,
+                    dex: event.dex.clone(),
+                    weight: 100,
+                    share_bps: 10_000,
+                    amount_in: Some(event.amount_in),
+                    amount_out: Some(event.amount_out),
+                }],
+                ..Default::default()
+            };
+            Ok(swap)
+        }
+    }?;
+    Ok((swap, event))
 }
 
 pub fn decode_raydiumlaunchpad_instruction(
     okx_data: Vec<u8>,
     accounts: Vec<Pubkey>,
     program_id: Pubkey,
-) -> Option<SwapTransaction> {
-    let decoder = RaydiumLaunchpadDecoder;
+) -> Result<SwapTransaction, DecodeError> {
+    decode_raydiumlaunchpad_instruction_with_metas(okx_data, readonly_metas(&accounts), program_id)
+}
 
-    let account_metas: Vec<AccountMeta> = accounts
-        .iter()
-        .map(|pubkey| AccountMeta {
-            pubkey: *pubkey,
-            is_signer: false,   // Defina como true se a conta for um signatário
-            is_writable: false, // Defina como true se a conta for gravável
-        })
-        .collect();
+/// Like [`decode_raydiumlaunchpad_instruction`], but keeps the signer and writable
+/// flags of the accounts.
+pub fn decode_raydiumlaunchpad_instruction_with_metas(
+    okx_data: Vec<u8>,
+    accounts: Vec<AccountMeta>,
+    program_id: Pubkey,
+) -> Result<SwapTransaction, DecodeError> {
+    check_program(carbon_raydium_launchpad_decoder::PROGRAM_ID, program_id)?;
+
+    let decoder = RaydiumLaunchpadDecoder;
 
     let instruction = Instruction {
         program_id,
-        accounts: account_metas, // Adicione as contas necessárias
-        data: okx_data,          // Adicione os dados da instrução
+        accounts,
+        data: okx_data, // Adicione os dados da instrução
     };
 
-    match decoder.decode_instruction(&instruction) {
-        Some(decoded_instruction) => match decoded_instruction.data {
-            RaydiumLaunchpadInstruction::BuyExactIn(ref swap_data) => {
-                let arranged_accounts =
-                    BuyExactIn::arrange_accounts(&instruction.accounts).unwrap();
-                let swap = SwapTransaction {
-                    amm: Some(arranged_accounts.program),
-                    in_amount: swap_data.amount_in,
-                    out_amount: Some(swap_data.minimum_amount_out),
-                    mint_token_in: Some(arranged_accounts.base_token_mint),
-                    mint_token_out: Some(arranged_accounts.quote_token_mint),
-                    mint_token_account_in: Some(arranged_accounts.user_base_token),
-                    mint_token_account_out: Some(arranged_accounts.user_quote_token),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            RaydiumLaunchpadInstruction::BuyExactOut(ref swap_data) => {
-                let arranged_accounts =
-                    BuyExactOut::arrange_accounts(&instruction.accounts).unwrap();
-                let swap = SwapTransaction {
-                    amm: Some(arranged_accounts.program),
-                    in_amount: swap_data.amount_out,
-                    out_amount: Some(swap_data.maximum_amount_in),
-                    mint_token_in: Some(arranged_accounts.base_token_mint),
-                    mint_token_out: Some(arranged_accounts.quote_token_mint),
-                    mint_token_account_in: Some(arranged_accounts.user_base_token),
-                    mint_token_account_out: Some(arranged_accounts.user_quote_token),
-                    user: None,
-                    mint: None,
-                    create_instruction_accounts: None,
-                };
-
-                return Some(swap);
-            }
-            _ => {
-                return None;
-            }
-        },
-        None => {
-            return None;
+    let decoded_instruction = decoder.decode_instruction(&instruction).ok_or_else(|| {
+        decode_failure(
+            Protocol::RaydiumLaunchpad,
+            &instruction.data,
+            &[
+                known::<BuyExactIn>(),
+                known::<BuyExactOut>(),
+                known::<SellExactIn>(),
+                known::<SellExactOut>(),
+                known::<Initialize>(),
+                known::<MigrateToAmm>(),
+                known::<MigrateToCpswap>(),
+            ],
+        )
+    })?;
+
+    // The migrations pass no program account, so every arm reports the
+    // program ID the instruction was sent to.
+    match decoded_instruction.data {
+        RaydiumLaunchpadInstruction::BuyExactIn(ref swap_data) => {
+            let arranged_accounts = arrange_accounts::<BuyExactIn>(&instruction.accounts)?;
+            let swap = SwapTransaction {
+                amm: Some(decoded_instruction.program_id),
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(swap_data.amount_in),
+                min_amount_out: Some(swap_data.minimum_amount_out),
+                mint_token_in: Some(arranged_accounts.quote_token_mint),
+                mint_token_out: Some(arranged_accounts.base_token_mint),
+                mint_token_account_in: Some(arranged_accounts.user_quote_token),
+                mint_token_account_out: Some(arranged_accounts.user_base_token),
+                user: Some(arranged_accounts.payer),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        RaydiumLaunchpadInstruction::BuyExactOut(ref swap_data) => {
+            let arranged_accounts = arrange_accounts::<BuyExactOut>(&instruction.accounts)?;
+            let swap = SwapTransaction {
+                amm: Some(decoded_instruction.program_id),
+                swap_mode: Some(SwapMode::ExactOut),
+                max_amount_in: Some(swap_data.maximum_amount_in),
+                amount_out: Some(swap_data.amount_out),
+                mint_token_in: Some(arranged_accounts.quote_token_mint),
+                mint_token_out: Some(arranged_accounts.base_token_mint),
+                mint_token_account_in: Some(arranged_accounts.user_quote_token),
+                mint_token_account_out: Some(arranged_accounts.user_base_token),
+                user: Some(arranged_accounts.payer),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        RaydiumLaunchpadInstruction::SellExactIn(ref swap_data) => {
+            let arranged_accounts = arrange_accounts::<SellExactIn>(&instruction.accounts)?;
+            let swap = SwapTransaction {
+                amm: Some(decoded_instruction.program_id),
+                swap_mode: Some(SwapMode::ExactIn),
+                amount_in: Some(swap_data.amount_in),
+                min_amount_out: Some(swap_data.minimum_amount_out),
+                mint_token_in: Some(arranged_accounts.base_token_mint),
+                mint_token_out: Some(arranged_accounts.quote_token_mint),
+                mint_token_account_in: Some(arranged_accounts.user_base_token),
+                mint_token_account_out: Some(arranged_accounts.user_quote_token),
+                user: Some(arranged_accounts.payer),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        RaydiumLaunchpadInstruction::SellExactOut(ref swap_data) => {
+            let arranged_accounts = arrange_accounts::<SellExactOut>(&instruction.accounts)?;
+            let swap = SwapTransaction {
+                amm: Some(decoded_instruction.program_id),
+                swap_mode: Some(SwapMode::ExactOut),
+                max_amount_in: Some(swap_data.maximum_amount_in),
+                amount_out: Some(swap_data.amount_out),
+                mint_token_in: Some(arranged_accounts.base_token_mint),
+                mint_token_out: Some(arranged_accounts.quote_token_mint),
+                mint_token_account_in: Some(arranged_accounts.user_base_token),
+                mint_token_account_out: Some(arranged_accounts.user_quote_token),
+                user: Some(arranged_accounts.payer),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        RaydiumLaunchpadInstruction::Initialize(ref data) => {
+            let arranged_accounts = arrange_accounts::<Initialize>(&instruction.accounts)?;
+            let swap = SwapTransaction {
+                amm: Some(decoded_instruction.program_id),
+                user: Some(arranged_accounts.creator),
+                mint: Some(arranged_accounts.base_mint),
+                lifecycle: Some(LifecycleEvent {
+                    stage: LifecycleStage::Launched,
+                    pool: arranged_accounts.pool_state,
+                    base_mint: arranged_accounts.base_mint,
+                    quote_mint: arranged_accounts.quote_mint,
+                    destination_pool: None,
+                    metadata: Some(TokenMetadata {
+                        name: data.base_mint_param.name.clone(),
+                        symbol: data.base_mint_param.symbol.clone(),
+                        uri: data.base_mint_param.uri.clone(),
+                        decimals: data.base_mint_param.decimals,
+                    }),
+                }),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        RaydiumLaunchpadInstruction::MigrateToAmm(_) => {
+            let arranged_accounts = arrange_accounts::<MigrateToAmm>(&instruction.accounts)?;
+            let swap = SwapTransaction {
+                amm: Some(decoded_instruction.program_id),
+                user: Some(arranged_accounts.payer),
+                mint: Some(arranged_accounts.base_mint),
+                lifecycle: Some(LifecycleEvent {
+                    stage: LifecycleStage::MigratedToAmm,
+                    pool: arranged_accounts.pool_state,
+                    base_mint: arranged_accounts.base_mint,
+                    quote_mint: arranged_accounts.quote_mint,
+                    destination_pool: Some(arranged_accounts.amm_pool),
+                    metadata: None,
+                }),
+                ..Default::default()
+            };
+
+            Ok(swap)
+        }
+        RaydiumLaunchpadInstruction::MigrateToCpswap(_) => {
+            let arranged_accounts = arrange_accounts::<MigrateToCpswap>(&instruction.accounts)?;
+            let swap = SwapTransaction {
+                amm: Some(decoded_instruction.program_id),
+                user: Some(arranged_accounts.payer),
+                mint: Some(arranged_accounts.base_mint),
+                lifecycle: Some(LifecycleEvent {
+                    stage: LifecycleStage::MigratedToCpswap,
+                    pool: arranged_accounts.pool_state,
+                    base_mint: arranged_accounts.base_mint,
+                    quote_mint: arranged_accounts.quote_mint,
+                    destination_pool: Some(arranged_accounts.cpswap_pool),
+                    metadata: None,
+                }),
+                ..Default::default()
+            };
+
+            Ok(swap)
         }
+        other => Err(unsupported_variant(
+            Protocol::RaydiumLaunchpad,
+            other.get_instruction_type(),
+        )),
     }
 }
@@ -0,0 +1,61 @@
+use solana_sdk::message::v0::MessageAddressTableLookup;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+
+use crate::DecodeError;
+
+/// Supplies the contents of address lookup tables when a transaction comes
+/// without `loaded_addresses` in its meta.
+pub trait LookupTableResolver {
+    /// Returns every address stored in `table`, or `None` if it is unknown.
+    fn resolve(&self, table: &Pubkey) -> Option<Vec<Pubkey>>;
+}
+
+impl LookupTableResolver for HashMap<Pubkey, Vec<Pubkey>> {
+    fn resolve(&self, table: &Pubkey) -> Option<Vec<Pubkey>> {
+        self.get(table).cloned()
+    }
+}
+
+/// Loads the addresses referenced by `lookups`, split into the writable and
+/// readonly lists the runtime appends, in that order, to the account list.
+pub fn resolve_lookups(
+    lookups: &[MessageAddressTableLookup],
+    resolver: &dyn LookupTableResolver,
+) -> Result<(Vec<Pubkey>, Vec<Pubkey>), DecodeError> {
+    let tables = lookups
+        .iter()
+        .map(|lookup| {
+            resolver
+                .resolve(&lookup.account_key)
+                .ok_or(DecodeError::MissingLookupTable(lookup.account_key))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut writable = Vec::new();
+    let mut readonly = Vec::new();
+    for (lookup, addresses) in lookups.iter().zip(&tables) {
+        for index in &lookup.writable_indexes {
+            writable.push(lookup_address(lookup, addresses, *index)?);
+        }
+        for index in &lookup.readonly_indexes {
+            readonly.push(lookup_address(lookup, addresses, *index)?);
+        }
+    }
+
+    Ok((writable, readonly))
+}
+
+fn lookup_address(
+    lookup: &MessageAddressTableLookup,
+    addresses: &[Pubkey],
+    index: u8,
+) -> Result<Pubkey, DecodeError> {
+    addresses
+        .get(index as usize)
+        .copied()
+        .ok_or(DecodeError::LookupTableIndexOutOfBounds {
+            table: lookup.account_key,
+            index,
+        })
+}
@@ -1,43 +1,6 @@
-use carbon_core::account::AccountDecoder;
-use carbon_core::deserialize::ArrangeAccounts;
-use carbon_core::instruction::InstructionDecoder;
-use carbon_core::{borsh, CarbonDeserialize};
-use carbon_jupiter_swap_decoder::accounts::JupiterSwapAccount;
-use carbon_jupiter_swap_decoder::instructions::route::Route;
-use carbon_jupiter_swap_decoder::instructions::shared_accounts_exact_out_route::SharedAccountsExactOutRoute;
-use carbon_jupiter_swap_decoder::instructions::shared_accounts_route::SharedAccountsRoute;
-use carbon_jupiter_swap_decoder::instructions::swap_event::SwapEvent;
-use carbon_jupiter_swap_decoder::instructions::JupiterSwapInstruction;
-use carbon_jupiter_swap_decoder::types;
-use carbon_jupiter_swap_decoder::JupiterSwapDecoder;
-use carbon_okx_dex_decoder::accounts::OkxDexAccount;
-use carbon_okx_dex_decoder::instructions::commission_spl_swap::CommissionSplSwap;
-use carbon_okx_dex_decoder::instructions::commission_spl_swap2::CommissionSplSwap2;
-use carbon_okx_dex_decoder::instructions::swap::Swap;
-use carbon_okx_dex_decoder::instructions::swap2::Swap2;
-use carbon_okx_dex_decoder::instructions::OkxDexInstruction;
-use carbon_okx_dex_decoder::OkxDexDecoder;
-use carbon_raydium_amm_v4_decoder::instructions::swap_base_in::SwapBaseIn;
-use carbon_raydium_amm_v4_decoder::instructions::swap_base_out::SwapBaseOut;
-use carbon_raydium_amm_v4_decoder::instructions::RaydiumAmmV4Instruction;
-
-use carbon_raydium_amm_v4_decoder::RaydiumAmmV4Decoder;
-use serde::{Deserialize, Serialize};
-use serde_json::json;
-use solana_sdk::account::Account;
-use solana_sdk::instruction::{AccountMeta, Instruction};
 use solana_sdk::pubkey::Pubkey;
-use std::convert::TryInto;
-use std::mem::size_of;
 use std::str::FromStr;
 
-#[derive(Serialize, Deserialize, Debug)]
-struct TransactionDataRaydium2 {
-    discriminator: u8,
-    amount_in: u64,
-    minimum_amount_out: u64,
-}
-
 fn main() {
     // let data_simple: [u8; 16] = [0, 0, 0, 0, 0, 0, 0, 0, 57, 190, 133, 229, 1, 0, 0, 0];
 
@@ -196,17 +159,16 @@ fn main() {
     let program_id_launchpad = Pubkey::from_str("LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj"); // Exemplo de PROGRA
 
 
-    let swap_transaction = match teste::decode_raydiumlaunchpad_instruction(
+    match teste::decode_instruction(
         raydiumlaunchpad_data,
         accounts_raydiumlaunchpad.to_vec(),
         program_id_launchpad.unwrap(),
     ) {
-        Some(transaction) => {
+        Ok(transaction) => {
             println!("{:?}", transaction); // ✅ Sucesso: pega o valor e imprime
         }
-        None => {
-            println!("❌ Erro: Falha ao decodificar a instrução OKX.");
-            return; // Ou retorne um valor padrão
+        Err(err) => {
+            println!("❌ Erro: Falha ao decodificar a instrução: {}", err);
         }
     };
 
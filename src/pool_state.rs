@@ -0,0 +1,161 @@
+use carbon_core::account::AccountDecoder;
+use carbon_raydium_amm_v4_decoder::accounts::amm_info::AmmInfo;
+use carbon_raydium_amm_v4_decoder::accounts::RaydiumAmmV4Account;
+use carbon_raydium_amm_v4_decoder::RaydiumAmmV4Decoder;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_response::RpcKeyedAccount;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use crate::balances::BalanceChanges;
+use crate::{DecodeError, SwapTransaction};
+
+/// Supplies the on-chain state of Raydium AMM v4 pools, which the swap
+/// instructions reference without naming their mints.
+pub trait PoolStateProvider {
+    /// Returns the decoded `AmmInfo` of `amm`, or `None` if it is unknown.
+    fn amm_info(&self, amm: &Pubkey) -> Option<AmmInfo>;
+}
+
+impl PoolStateProvider for HashMap<Pubkey, AmmInfo> {
+    fn amm_info(&self, amm: &Pubkey) -> Option<AmmInfo> {
+        self.get(amm).cloned()
+    }
+}
+
+/// In-memory cache of pool states. Misses are fetched from RPC when a client
+/// is configured; otherwise only preloaded pools are known.
+#[derive(Default)]
+pub struct PoolStateCache {
+    pools: RwLock<HashMap<Pubkey, AmmInfo>>,
+    rpc: Option<RpcClient>,
+}
+
+impl PoolStateCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rpc(rpc: RpcClient) -> Self {
+        PoolStateCache {
+            pools: RwLock::default(),
+            rpc: Some(rpc),
+        }
+    }
+
+    /// Loads a snapshot file holding a JSON array of `{ "pubkey", "account" }`
+    /// entries, as returned by `getProgramAccounts` or `getMultipleAccounts`.
+    /// Accounts that are not AMM pools are skipped.
+    pub fn from_snapshot(path: impl AsRef<Path>) -> Result<Self, DecodeError> {
+        let cache = Self::new();
+        cache.load_snapshot(path)?;
+        Ok(cache)
+    }
+
+    pub fn load_snapshot(&self, path: impl AsRef<Path>) -> Result<usize, DecodeError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| DecodeError::InvalidSnapshot(format!("{}: {err}", path.display())))?;
+        let entries: Vec<RpcKeyedAccount> = serde_json::from_str(&contents)
+            .map_err(|err| DecodeError::InvalidSnapshot(format!("{}: {err}", path.display())))?;
+
+        let mut loaded = 0;
+        for entry in entries {
+            let pubkey = Pubkey::from_str(&entry.pubkey).map_err(|_| {
+                DecodeError::InvalidSnapshot(format!("bad pubkey {}", entry.pubkey))
+            })?;
+            let Some(account) = entry.account.decode::<Account>() else {
+                continue;
+            };
+            if self.insert_account(pubkey, &account) {
+                loaded += 1;
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Decodes `account` and caches it if it is an `AmmInfo`.
+    pub fn insert_account(&self, pubkey: Pubkey, account: &Account) -> bool {
+        let Some(amm_info) = decode_amm_info(account) else {
+            return false;
+        };
+        self.insert(pubkey, amm_info);
+        true
+    }
+
+    pub fn insert(&self, pubkey: Pubkey, amm_info: AmmInfo) {
+        self.pools
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(pubkey, amm_info);
+    }
+
+    fn cached(&self, amm: &Pubkey) -> Option<AmmInfo> {
+        self.pools
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(amm)
+            .cloned()
+    }
+}
+
+impl PoolStateProvider for PoolStateCache {
+    fn amm_info(&self, amm: &Pubkey) -> Option<AmmInfo> {
+        if let Some(amm_info) = self.cached(amm) {
+            return Some(amm_info);
+        }
+
+        let account = self.rpc.as_ref()?.get_account(amm).ok()?;
+        let amm_info = decode_amm_info(&account)?;
+        self.insert(*amm, amm_info.clone());
+        Some(amm_info)
+    }
+}
+
+fn decode_amm_info(account: &Account) -> Option<AmmInfo> {
+    match RaydiumAmmV4Decoder.decode_account(account)?.data {
+        RaydiumAmmV4Account::AmmInfo(amm_info) => Some(amm_info),
+        _ => None,
+    }
+}
+
+/// Fills the mints of a Raydium AMM v4 swap from its pool state. The
+/// instruction does not say which side is sold, so the direction comes from
+/// the mint of the user's source account or, failing that, from whether the
+/// pool's coin vault grew.
+pub(crate) fn fill_raydium_mints(
+    swap: &mut SwapTransaction,
+    pools: &dyn PoolStateProvider,
+    balances: Option<&BalanceChanges>,
+) {
+    let (Some(amm), Some(balances)) = (swap.amm, balances) else {
+        return;
+    };
+    let Some(pool) = pools.amm_info(&amm) else {
+        return;
+    };
+
+    let source_mint = swap
+        .mint_token_account_in
+        .and_then(|account| balances.mint(&account));
+    let coin_in = match source_mint {
+        Some(mint) if mint == pool.coin_mint => true,
+        Some(mint) if mint == pool.pc_mint => false,
+        _ => match balances.delta(&pool.token_coin) {
+            Some(delta) if delta != 0 => delta > 0,
+            _ => return,
+        },
+    };
+
+    let (mint_in, mint_out) = if coin_in {
+        (pool.coin_mint, pool.pc_mint)
+    } else {
+        (pool.pc_mint, pool.coin_mint)
+    };
+    swap.mint_token_in = Some(mint_in);
+    swap.mint_token_out = Some(mint_out);
+}
@@ -0,0 +1,59 @@
+use carbon_okx_dex_decoder::programs::OkxPrograms;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Protocol {
+    RaydiumAmmV4,
+    Pumpfun,
+    PumpSwap,
+    Jupiter,
+    Okx,
+    RaydiumLaunchpad,
+}
+
+/// Every program the crate knows how to decode, keyed by program ID, except
+/// the OKX router, whose deployments come from an [`OkxPrograms`] set.
+pub const PROTOCOLS: &[(Pubkey, Protocol)] = &[
+    (
+        carbon_raydium_amm_v4_decoder::PROGRAM_ID,
+        Protocol::RaydiumAmmV4,
+    ),
+    (carbon_pumpfun_decoder::PROGRAM_ID, Protocol::Pumpfun),
+    (carbon_pump_swap_decoder::PROGRAM_ID, Protocol::PumpSwap),
+    (carbon_jupiter_swap_decoder::PROGRAM_ID, Protocol::Jupiter),
+    (
+        carbon_raydium_launchpad_decoder::PROGRAM_ID,
+        Protocol::RaydiumLaunchpad,
+    ),
+];
+
+impl Protocol {
+    /// Recognises the OKX router by its mainnet program ID only; see
+    /// [`Protocol::from_program_id_in`].
+    pub fn from_program_id(program_id: &Pubkey) -> Option<Protocol> {
+        Protocol::from_program_id_in(program_id, &OkxPrograms::default())
+    }
+
+    /// Like [`Protocol::from_program_id`], accepting the OKX router
+    /// deployments in `okx_programs`.
+    pub fn from_program_id_in(program_id: &Pubkey, okx_programs: &OkxPrograms) -> Option<Protocol> {
+        PROTOCOLS
+            .iter()
+            .find(|(id, _)| id == program_id)
+            .map(|(_, protocol)| *protocol)
+            .or_else(|| okx_programs.contains(program_id).then_some(Protocol::Okx))
+    }
+
+    /// The mainnet program ID of the protocol.
+    pub fn program_id(&self) -> Pubkey {
+        if *self == Protocol::Okx {
+            return carbon_okx_dex_decoder::PROGRAM_ID;
+        }
+        PROTOCOLS
+            .iter()
+            .find(|(_, protocol)| protocol == self)
+            .map(|(id, _)| *id)
+            .expect("every protocol is registered")
+    }
+}
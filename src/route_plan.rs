@@ -0,0 +1,62 @@
+use carbon_jupiter_swap_decoder::instructions::swap_event::SwapEvent;
+use carbon_jupiter_swap_decoder::types::{RoutePlanStep, Swap};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+/// One step of a Jupiter route plan. Jupiter tracks intermediate amounts by
+/// position: a hop spends `percent` of the amount at `input_index` and adds
+/// its output to `output_index`. Hops sharing an input are splits; a hop
+/// reading another hop's output follows it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct JupiterHop {
+    /// Position of the hop in the route plan.
+    pub step: usize,
+    /// AMM the hop swaps through, with its AMM-specific parameters.
+    pub swap: Swap,
+    pub percent: u8,
+    pub input_index: u8,
+    pub output_index: u8,
+    /// AMM account that executed the hop, from the matching swap event. The
+    /// remaining fields come from the same event.
+    pub amm: Option<Pubkey>,
+    pub input_mint: Option<Pubkey>,
+    pub output_mint: Option<Pubkey>,
+    pub amount_in: Option<u64>,
+    pub amount_out: Option<u64>,
+}
+
+/// Expands a route plan into hops, in execution order.
+pub fn jupiter_hops(route_plan: &[RoutePlanStep]) -> Vec<JupiterHop> {
+    route_plan
+        .iter()
+        .enumerate()
+        .map(|(step, plan_step)| JupiterHop {
+            step,
+            swap: plan_step.swap.clone(),
+            percent: plan_step.percent,
+            input_index: plan_step.input_index,
+            output_index: plan_step.output_index,
+            amm: None,
+            input_mint: None,
+            output_mint: None,
+            amount_in: None,
+            amount_out: None,
+        })
+        .collect()
+}
+
+/// Fills `hops` from the swap events the route emitted. Jupiter emits one
+/// event per executed step, in route plan order; returns how many hops were
+/// filled.
+pub fn apply_swap_events(hops: &mut [JupiterHop], events: &[SwapEvent]) -> usize {
+    let mut filled = 0;
+    for (hop, event) in hops.iter_mut().zip(events) {
+        hop.amm = Some(event.amm);
+        hop.input_mint = Some(event.input_mint);
+        hop.output_mint = Some(event.output_mint);
+        hop.amount_in = Some(event.input_amount);
+        hop.amount_out = Some(event.output_amount);
+        filled += 1;
+    }
+    filled
+}
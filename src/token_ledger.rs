@@ -0,0 +1,169 @@
+use carbon_core::deserialize::CarbonDeserialize;
+use carbon_jupiter_swap_decoder::instructions::set_token_ledger::SetTokenLedger;
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::transaction::InstructionSwap;
+
+const TOKEN_PROGRAM: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const TOKEN_2022_PROGRAM: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+const SYSTEM_PROGRAM: Pubkey = pubkey!("11111111111111111111111111111111");
+
+const TRANSFER: u8 = 3;
+const TRANSFER_CHECKED: u8 = 12;
+const SYSTEM_TRANSFER: u32 = 2;
+
+/// A transfer from `source` to `destination` at `path` in the transaction:
+/// tokens between token accounts, or lamports moved by the system program.
+pub(crate) struct TokenTransfer {
+    pub path: Vec<usize>,
+    pub source: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+/// A Jupiter `SetTokenLedger`, which records the current balance of
+/// `token_account` in `token_ledger`.
+pub(crate) struct LedgerSnapshot {
+    pub path: Vec<usize>,
+    pub token_ledger: Pubkey,
+    pub token_account: Pubkey,
+}
+
+/// Reads a `Transfer` or `TransferChecked` of either token program, or a
+/// system program `Transfer`. All take the source account first and the
+/// amount right after the tag; `TransferChecked` puts the mint before the
+/// destination.
+pub(crate) fn token_transfer(
+    path: &[usize],
+    program_id: &Pubkey,
+    accounts: &[Pubkey],
+    data: &[u8],
+) -> Option<TokenTransfer> {
+    let (destination, amount) = if *program_id == SYSTEM_PROGRAM {
+        let tag = u32::from_le_bytes(data.get(..4)?.try_into().ok()?);
+        if tag != SYSTEM_TRANSFER {
+            return None;
+        }
+        (accounts.get(1)?, data.get(4..12)?)
+    } else if *program_id == TOKEN_PROGRAM || *program_id == TOKEN_2022_PROGRAM {
+        match *data.first()? {
+            TRANSFER => (accounts.get(1)?, data.get(1..9)?),
+            TRANSFER_CHECKED => (accounts.get(2)?, data.get(1..9)?),
+            _ => return None,
+        }
+    } else {
+        return None;
+    };
+
+    Some(TokenTransfer {
+        path: path.to_vec(),
+        source: *accounts.first()?,
+        destination: *destination,
+        amount: u64::from_le_bytes(amount.try_into().ok()?),
+    })
+}
+
+/// Total of the transfers made by the instruction at `path` or its CPIs that
+/// `matches` accepts, or `None` when there are none.
+pub(crate) fn routed_amount(
+    transfers: &[TokenTransfer],
+    path: &[usize],
+    matches: impl Fn(&TokenTransfer) -> bool,
+) -> Option<u64> {
+    let mut routed = transfers
+        .iter()
+        .filter(|transfer| {
+            transfer.path.len() > path.len() && transfer.path.starts_with(path) && matches(transfer)
+        })
+        .peekable();
+    routed.peek()?;
+    routed.try_fold(0u64, |sum, transfer| sum.checked_add(transfer.amount))
+}
+
+pub(crate) fn ledger_snapshot(
+    path: &[usize],
+    program_id: &Pubkey,
+    accounts: &[Pubkey],
+    data: &[u8],
+) -> Option<LedgerSnapshot> {
+    if *program_id != carbon_jupiter_swap_decoder::PROGRAM_ID {
+        return None;
+    }
+    SetTokenLedger::deserialize(data)?;
+    let [token_ledger, token_account, ..] = accounts else {
+        return None;
+    };
+
+    Some(LedgerSnapshot {
+        path: path.to_vec(),
+        token_ledger: *token_ledger,
+        token_account: *token_account,
+    })
+}
+
+/// Fills the input of Jupiter token-ledger routes, which swap whatever their
+/// source account gained since the ledger snapshot and so carry no amount.
+/// The input is what the route transferred out of the snapshotted account,
+/// or, without inner instructions, what the instructions between the
+/// snapshot and the route moved into it.
+pub(crate) fn fill_ledger_inputs(
+    swaps: &mut [InstructionSwap],
+    snapshots: &[LedgerSnapshot],
+    transfers: &[TokenTransfer],
+) {
+    for instruction_swap in swaps {
+        let Some(token_ledger) = instruction_swap.swap.token_ledger else {
+            continue;
+        };
+
+        let path = &instruction_swap.path;
+        let snapshot = snapshots
+            .iter()
+            .rev()
+            .find(|snapshot| snapshot.token_ledger == token_ledger && snapshot.path < *path);
+        let Some(source) = snapshot
+            .map(|snapshot| snapshot.token_account)
+            .or(instruction_swap.swap.mint_token_account_in)
+        else {
+            continue;
+        };
+
+        let swap = &mut instruction_swap.swap;
+        match routed_amount(transfers, path, |transfer| transfer.source == source) {
+            Some(amount) => {
+                swap.amount_in = Some(amount);
+                swap.realized_in = Some(amount);
+            }
+            None => {
+                swap.amount_in = snapshot
+                    .and_then(|snapshot| net_inflow(transfers, &source, &snapshot.path, path))
+            }
+        }
+    }
+}
+
+/// Net amount the transfers strictly between `after` and `before` in the
+/// transaction moved into `account`, or `None` unless it grew.
+fn net_inflow(
+    transfers: &[TokenTransfer],
+    account: &Pubkey,
+    after: &[usize],
+    before: &[usize],
+) -> Option<u64> {
+    let net = transfers
+        .iter()
+        .filter(|transfer| transfer.path.as_slice() > after && transfer.path.as_slice() < before)
+        .fold(0i128, |net, transfer| {
+            let amount = transfer.amount as i128;
+            match (
+                transfer.destination == *account,
+                transfer.source == *account,
+            ) {
+                (true, false) => net + amount,
+                (false, true) => net - amount,
+                _ => net,
+            }
+        });
+    u64::try_from(net).ok().filter(|amount| *amount > 0)
+}
@@ -0,0 +1,670 @@
+use carbon_core::deserialize::CarbonDeserialize;
+use carbon_jupiter_swap_decoder::instructions::fee_event::FeeEvent as JupiterFeeEvent;
+use carbon_jupiter_swap_decoder::instructions::swap_event::SwapEvent as JupiterSwapEvent;
+use carbon_okx_dex_decoder::dex_programs::check_invoked_programs;
+use carbon_okx_dex_decoder::legs::{apply_events, executed_totals};
+use carbon_okx_dex_decoder::programs::OkxPrograms;
+use carbon_okx_dex_decoder::types::SwapEvent;
+use serde::{Deserialize, Serialize};
+use solana_sdk::bs58;
+use solana_sdk::instruction::{AccountMeta, CompiledInstruction as SdkCompiledInstruction};
+use solana_sdk::message::v0::MessageAddressTableLookup;
+use solana_sdk::message::MessageHeader;
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiInstruction, UiMessage,
+    UiTransactionStatusMeta,
+};
+use std::str::FromStr;
+
+use crate::balances::BalanceChanges;
+use crate::commission::{settle_output_commission, settle_platform_fee};
+use crate::lookup_table::{resolve_lookups, LookupTableResolver};
+use crate::pool_state::{fill_raydium_mints, PoolStateProvider};
+use crate::route_plan::apply_swap_events;
+use crate::token_ledger::{fill_ledger_inputs, ledger_snapshot, token_transfer};
+use crate::{
+    decode_instruction_with_metas, decode_swap_with_metas, DecodeError, Protocol, SwapTransaction,
+};
+
+/// A swap decoded from one instruction of a transaction. `path` is the outer
+/// instruction index followed by the CPI index at each nesting level, so
+/// `[2]` is the third outer instruction and `[2, 0]` its first CPI.
+///
+/// `parent_path` is the path of the closest Jupiter or OKX route the swap was
+/// made by, e.g. a Raydium pool one hop of a route traded through. Such
+/// swaps are part of their route's trade and should not be counted again.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct InstructionSwap {
+    pub path: Vec<usize>,
+    pub program_id: Pubkey,
+    pub swap: SwapTransaction,
+    pub parent_path: Option<Vec<usize>>,
+}
+
+/// An instruction of a supported program that could not be decoded.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct InstructionFailure {
+    pub path: Vec<usize>,
+    pub program_id: Pubkey,
+    pub error: DecodeError,
+}
+
+/// Swaps of a transaction. A transaction that failed on chain traded
+/// nothing, so it is reported as `failed` with no swaps or failures.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct TransactionSwaps {
+    pub swaps: Vec<InstructionSwap>,
+    pub failures: Vec<InstructionFailure>,
+    pub failed: bool,
+}
+
+/// Optional sources of on-chain state used while decoding a transaction.
+#[derive(Default, Clone, Copy)]
+pub struct DecodeContext<'a> {
+    /// Fallback for address lookup tables missing from the meta.
+    pub lookup_tables: Option<&'a dyn LookupTableResolver>,
+    /// Pool states used to fill the mints of Raydium AMM v4 swaps.
+    pub pool_state: Option<&'a dyn PoolStateProvider>,
+    /// OKX router deployments to decode; just the mainnet router when unset.
+    pub okx_programs: Option<&'a OkxPrograms>,
+}
+
+/// Account list of a transaction message: the static keys followed by the
+/// addresses loaded from lookup tables, with the header that tells signers
+/// and writable accounts apart.
+#[derive(Debug, Clone, Default)]
+pub struct MessageAccounts {
+    pub header: MessageHeader,
+    pub static_keys: Vec<Pubkey>,
+    pub loaded_writable: Vec<Pubkey>,
+    pub loaded_readonly: Vec<Pubkey>,
+}
+
+impl MessageAccounts {
+    /// Every account in the order compiled instructions index them.
+    pub fn keys(&self) -> Vec<Pubkey> {
+        self.static_keys
+            .iter()
+            .chain(&self.loaded_writable)
+            .chain(&self.loaded_readonly)
+            .copied()
+            .collect()
+    }
+
+    pub fn get(&self, index: u8) -> Option<Pubkey> {
+        let index = index as usize;
+        let loaded_index = index.checked_sub(self.static_keys.len());
+        match loaded_index {
+            None => self.static_keys.get(index),
+            Some(loaded_index) => self.loaded_writable.get(loaded_index).or_else(|| {
+                self.loaded_readonly
+                    .get(loaded_index - self.loaded_writable.len())
+            }),
+        }
+        .copied()
+    }
+
+    pub fn is_signer(&self, index: u8) -> bool {
+        index < self.header.num_required_signatures
+    }
+
+    /// Whether the message requests write access to the account. The runtime
+    /// may still demote program IDs and reserved accounts to readonly. A
+    /// header claiming more readonly accounts than there are makes them all
+    /// readonly.
+    pub fn is_writable(&self, index: u8) -> bool {
+        let index = index as usize;
+        let num_signed = self.header.num_required_signatures as usize;
+        if index < num_signed {
+            return index
+                < num_signed.saturating_sub(self.header.num_readonly_signed_accounts as usize);
+        }
+        if index < self.static_keys.len() {
+            return index
+                < self
+                    .static_keys
+                    .len()
+                    .saturating_sub(self.header.num_readonly_unsigned_accounts as usize);
+        }
+        index < self.static_keys.len() + self.loaded_writable.len()
+    }
+
+    pub fn account_meta(&self, index: u8) -> Result<AccountMeta, DecodeError> {
+        let pubkey = self
+            .get(index)
+            .ok_or(DecodeError::AccountIndexOutOfBounds { index })?;
+        Ok(AccountMeta {
+            pubkey,
+            is_signer: self.is_signer(index),
+            is_writable: self.is_writable(index),
+        })
+    }
+
+    fn account_metas(&self, indexes: &[u8]) -> Result<Vec<AccountMeta>, DecodeError> {
+        indexes
+            .iter()
+            .map(|index| self.account_meta(*index))
+            .collect()
+    }
+}
+
+/// Decodes one compiled instruction of a message, keeping the signer and
+/// writable flags its header implies.
+pub fn decode_compiled_instruction(
+    instruction: &SdkCompiledInstruction,
+    accounts: &MessageAccounts,
+) -> Result<SwapTransaction, DecodeError> {
+    let program_id =
+        accounts
+            .get(instruction.program_id_index)
+            .ok_or(DecodeError::AccountIndexOutOfBounds {
+                index: instruction.program_id_index,
+            })?;
+    let account_metas = accounts.account_metas(&instruction.accounts)?;
+    decode_instruction_with_metas(instruction.data.clone(), account_metas, program_id)
+}
+
+/// An instruction with its accounts still given as indexes into the
+/// transaction's account list.
+struct CompiledInstruction {
+    path: Vec<usize>,
+    program_id_index: u8,
+    accounts: Vec<u8>,
+    data: Vec<u8>,
+}
+
+struct TransactionMessage {
+    header: MessageHeader,
+    account_keys: Vec<Pubkey>,
+    address_table_lookups: Vec<MessageAddressTableLookup>,
+    instructions: Vec<CompiledInstruction>,
+}
+
+/// Decodes every outer and inner instruction of a confirmed transaction that
+/// belongs to a supported program. Instructions of those programs that are
+/// not swaps, such as events and setup instructions, are skipped.
+pub fn decode_transaction(
+    transaction: &EncodedConfirmedTransactionWithStatusMeta,
+) -> Result<TransactionSwaps, DecodeError> {
+    decode_transaction_with_resolver(transaction, None)
+}
+
+/// Like [`decode_transaction`], but falls back to `resolver` for the address
+/// lookup tables of a v0 transaction whose meta has no `loaded_addresses`.
+pub fn decode_transaction_with_resolver(
+    transaction: &EncodedConfirmedTransactionWithStatusMeta,
+    resolver: Option<&dyn LookupTableResolver>,
+) -> Result<TransactionSwaps, DecodeError> {
+    decode_transaction_with_context(
+        transaction,
+        DecodeContext {
+            lookup_tables: resolver,
+            ..Default::default()
+        },
+    )
+}
+
+/// Like [`decode_transaction`], using whatever state `context` provides.
+pub fn decode_transaction_with_context(
+    transaction: &EncodedConfirmedTransactionWithStatusMeta,
+    context: DecodeContext,
+) -> Result<TransactionSwaps, DecodeError> {
+    let default_okx_programs = OkxPrograms::default();
+    let okx_programs = context.okx_programs.unwrap_or(&default_okx_programs);
+    let message = read_message(&transaction.transaction.transaction)?;
+    let meta = transaction.transaction.meta.as_ref();
+    if meta.is_some_and(|meta| meta.err.is_some()) {
+        return Ok(TransactionSwaps {
+            failed: true,
+            ..Default::default()
+        });
+    }
+    let accounts = load_accounts(&message, meta, context.lookup_tables)?;
+    let account_keys = accounts.keys();
+    let mut instructions = message.instructions;
+
+    if let Some(meta) = meta {
+        instructions = nest_inner_instructions(instructions, meta)?;
+    }
+
+    let balances = meta.map(|meta| BalanceChanges::new(&account_keys, meta));
+    let has_inner_instructions =
+        meta.is_some_and(|meta| matches!(meta.inner_instructions, OptionSerializer::Some(_)));
+    let mut invoked: Vec<(Vec<usize>, Pubkey)> = Vec::new();
+    let mut transfers = Vec::new();
+    let mut ledger_snapshots = Vec::new();
+    for instruction in &instructions {
+        let Some(program_id) = accounts.get(instruction.program_id_index) else {
+            continue;
+        };
+        let keys: Vec<Pubkey> = instruction
+            .accounts
+            .iter()
+            .filter_map(|index| accounts.get(*index))
+            .collect();
+        let path = &instruction.path;
+        transfers.extend(token_transfer(path, &program_id, &keys, &instruction.data));
+        ledger_snapshots.extend(ledger_snapshot(path, &program_id, &keys, &instruction.data));
+        invoked.push((path.clone(), program_id));
+    }
+
+    let mut result = TransactionSwaps::default();
+    let mut okx_events = Vec::new();
+    let mut jupiter_events = Vec::new();
+    let mut jupiter_fee_events = Vec::new();
+    for instruction in instructions {
+        let Some(program_id) = accounts.get(instruction.program_id_index) else {
+            continue;
+        };
+        if Protocol::from_program_id_in(&program_id, okx_programs).is_none() {
+            continue;
+        }
+
+        if program_id == carbon_jupiter_swap_decoder::PROGRAM_ID {
+            if let Some(event) =
+                <JupiterFeeEvent as CarbonDeserialize>::deserialize(&instruction.data)
+            {
+                jupiter_fee_events.push((instruction.path, event));
+                continue;
+            }
+        }
+        let jupiter_event = (program_id == carbon_jupiter_swap_decoder::PROGRAM_ID)
+            .then(|| <JupiterSwapEvent as CarbonDeserialize>::deserialize(&instruction.data))
+            .flatten();
+
+        let decoded = accounts
+            .account_metas(&instruction.accounts)
+            .and_then(|metas| {
+                decode_swap_with_metas(instruction.data, metas, program_id, okx_programs)
+            });
+
+        match decoded {
+            Ok((mut swap, okx_event)) => {
+                if let Some(balances) = &balances {
+                    balances.apply_transfers(&mut swap, &instruction.path, &transfers);
+                    balances.fill_mints(&mut swap);
+                    if program_id == carbon_jupiter_swap_decoder::PROGRAM_ID
+                        && !has_inner_instructions
+                    {
+                        settle_platform_fee(&mut swap, balances);
+                    }
+                }
+                if let Some(pools) = context.pool_state {
+                    if program_id == Protocol::RaydiumAmmV4.program_id() {
+                        fill_raydium_mints(&mut swap, pools, balances.as_ref());
+                    }
+                }
+                if let Some(event) = okx_event {
+                    okx_events.push((result.swaps.len(), event));
+                }
+                if let Some(event) = jupiter_event {
+                    jupiter_events.push((result.swaps.len(), event));
+                }
+                result.swaps.push(InstructionSwap {
+                    path: instruction.path,
+                    program_id,
+                    swap,
+                    parent_path: None,
+                });
+            }
+            // Events and setup instructions decode fine but are not swaps.
+            Err(DecodeError::UnsupportedVariant { .. }) => {}
+            Err(error) => result.failures.push(InstructionFailure {
+                path: instruction.path,
+                program_id,
+                error,
+            }),
+        }
+    }
+
+    if has_inner_instructions {
+        let event_indexes: Vec<usize> = okx_events.iter().map(|(index, _)| *index).collect();
+        check_okx_routes(&mut result.swaps, &event_indexes, &invoked, okx_programs);
+    }
+    let mut attached = attach_okx_events(&mut result.swaps, okx_events, okx_programs);
+    attached.extend(attach_jupiter_events(&mut result.swaps, jupiter_events));
+    attached.sort_unstable();
+    for index in attached.into_iter().rev() {
+        result.swaps.remove(index);
+    }
+    link_routed_swaps(&mut result.swaps, okx_programs);
+    attach_jupiter_fee_events(&mut result.swaps, jupiter_fee_events);
+    fill_ledger_inputs(&mut result.swaps, &ledger_snapshots, &transfers);
+    for instruction_swap in &mut result.swaps {
+        if okx_programs.contains(&instruction_swap.program_id) {
+            settle_output_commission(&mut instruction_swap.swap);
+        }
+    }
+    Ok(result)
+}
+
+/// Sets the `parent_path` of every swap made inside a Jupiter or OKX route.
+/// Swaps are in instruction order, so a route comes before its CPIs.
+fn link_routed_swaps(swaps: &mut [InstructionSwap], okx_programs: &OkxPrograms) {
+    for index in 0..swaps.len() {
+        let path = &swaps[index].path;
+        let parent_path = swaps[..index]
+            .iter()
+            .rev()
+            .find(|parent| {
+                (parent.program_id == carbon_jupiter_swap_decoder::PROGRAM_ID
+                    || okx_programs.contains(&parent.program_id))
+                    && parent.path.len() < path.len()
+                    && path.starts_with(&parent.path)
+            })
+            .map(|parent| parent.path.clone());
+        swaps[index].parent_path = parent_path;
+    }
+}
+
+/// Flags OKX swaps whose direct CPIs do not match the DEXes their route
+/// declares.
+fn check_okx_routes(
+    swaps: &mut [InstructionSwap],
+    event_indexes: &[usize],
+    invoked: &[(Vec<usize>, Pubkey)],
+    okx_programs: &OkxPrograms,
+) {
+    for (index, instruction_swap) in swaps.iter_mut().enumerate() {
+        if !okx_programs.contains(&instruction_swap.program_id)
+            || instruction_swap.swap.okx_legs.is_empty()
+            || event_indexes.contains(&index)
+        {
+            continue;
+        }
+
+        let path = &instruction_swap.path;
+        let children: Vec<Pubkey> = invoked
+            .iter()
+            .filter(|(child, _)| child.len() == path.len() + 1 && child.starts_with(path))
+            .map(|(_, program_id)| *program_id)
+            .collect();
+        instruction_swap.swap.route_mismatches =
+            check_invoked_programs(&instruction_swap.swap.okx_legs, &children, okx_programs);
+    }
+}
+
+/// Moves each OKX swap event onto the closest OKX route that invoked it,
+/// filling the executed amounts of the route's legs and, once every leg is
+/// matched, its realized totals. Returns the indexes of the attached events
+/// for the caller to drop; events without such a route are kept as swaps of
+/// their own.
+fn attach_okx_events(
+    swaps: &mut [InstructionSwap],
+    events: Vec<(usize, SwapEvent)>,
+    okx_programs: &OkxPrograms,
+) -> Vec<usize> {
+    let event_indexes: Vec<usize> = events.iter().map(|(index, _)| *index).collect();
+    let mut grouped: Vec<(usize, Vec<SwapEvent>)> = Vec::new();
+    let mut attached = Vec::new();
+
+    for (event_index, event) in events {
+        let event_path = &swaps[event_index].path;
+        let Some(parent_index) = (0..event_index).rev().find(|index| {
+            let parent = &swaps[*index];
+            okx_programs.contains(&parent.program_id)
+                && parent.path.len() < event_path.len()
+                && event_path.starts_with(&parent.path)
+                && !parent.swap.okx_legs.is_empty()
+                && !event_indexes.contains(index)
+        }) else {
+            continue;
+        };
+
+        match grouped.iter_mut().find(|(index, _)| *index == parent_index) {
+            Some((_, parent_events)) => parent_events.push(event),
+            None => grouped.push((parent_index, vec![event])),
+        }
+        attached.push(event_index);
+    }
+
+    for (parent_index, parent_events) in grouped {
+        let swap = &mut swaps[parent_index].swap;
+        apply_events(&mut swap.okx_legs, &parent_events);
+        if let Some((amount_in, amount_out)) = executed_totals(&swap.okx_legs) {
+            swap.realized_in = Some(amount_in);
+            swap.realized_out = Some(amount_out);
+        }
+    }
+
+    attached
+}
+
+/// Moves each Jupiter swap event onto the closest Jupiter route that invoked
+/// it, filling the route's hops in the order the events were emitted.
+/// Returns the indexes of the attached events, like [`attach_okx_events`].
+fn attach_jupiter_events(
+    swaps: &mut [InstructionSwap],
+    events: Vec<(usize, JupiterSwapEvent)>,
+) -> Vec<usize> {
+    let event_indexes: Vec<usize> = events.iter().map(|(index, _)| *index).collect();
+    let mut grouped: Vec<(usize, Vec<JupiterSwapEvent>)> = Vec::new();
+    let mut attached = Vec::new();
+
+    for (event_index, event) in events {
+        let event_path = &swaps[event_index].path;
+        let Some(parent_index) = (0..event_index).rev().find(|index| {
+            let parent = &swaps[*index];
+            parent.program_id == carbon_jupiter_swap_decoder::PROGRAM_ID
+                && parent.path.len() < event_path.len()
+                && event_path.starts_with(&parent.path)
+                && !parent.swap.jupiter_hops.is_empty()
+                && !event_indexes.contains(index)
+        }) else {
+            continue;
+        };
+
+        match grouped.iter_mut().find(|(index, _)| *index == parent_index) {
+            Some((_, parent_events)) => parent_events.push(event),
+            None => grouped.push((parent_index, vec![event])),
+        }
+        attached.push(event_index);
+    }
+
+    for (parent_index, parent_events) in grouped {
+        apply_swap_events(&mut swaps[parent_index].swap.jupiter_hops, &parent_events);
+    }
+
+    attached
+}
+
+/// Settles the platform fee of each Jupiter route from the fee events it
+/// emitted to the fee account, which count what that route alone paid.
+fn attach_jupiter_fee_events(
+    swaps: &mut [InstructionSwap],
+    events: Vec<(Vec<usize>, JupiterFeeEvent)>,
+) {
+    for (event_path, event) in events {
+        let Some(parent) = swaps.iter_mut().rev().find(|parent| {
+            parent.program_id == carbon_jupiter_swap_decoder::PROGRAM_ID
+                && parent.path.len() < event_path.len()
+                && event_path.starts_with(&parent.path)
+        }) else {
+            continue;
+        };
+        let Some(commission) = &mut parent.swap.commission else {
+            continue;
+        };
+        if commission.recipient == event.account {
+            commission.amount = Some(
+                commission
+                    .amount
+                    .map_or(event.amount, |amount| amount.saturating_add(event.amount)),
+            );
+        }
+    }
+}
+
+fn read_message(transaction: &EncodedTransaction) -> Result<TransactionMessage, DecodeError> {
+    if let Some(versioned) = transaction.decode() {
+        let message = &versioned.message;
+        return Ok(TransactionMessage {
+            header: *message.header(),
+            account_keys: message.static_account_keys().to_vec(),
+            address_table_lookups: message
+                .address_table_lookups()
+                .map(<[_]>::to_vec)
+                .unwrap_or_default(),
+            instructions: message
+                .instructions()
+                .iter()
+                .enumerate()
+                .map(|(index, instruction)| CompiledInstruction {
+                    path: vec![index],
+                    program_id_index: instruction.program_id_index,
+                    accounts: instruction.accounts.clone(),
+                    data: instruction.data.clone(),
+                })
+                .collect(),
+        });
+    }
+
+    let EncodedTransaction::Json(ui_transaction) = transaction else {
+        return Err(DecodeError::UnsupportedEncoding);
+    };
+    let UiMessage::Raw(raw) = &ui_transaction.message else {
+        return Err(DecodeError::UnsupportedEncoding);
+    };
+
+    let account_keys = raw
+        .account_keys
+        .iter()
+        .map(|key| parse_pubkey(key))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let address_table_lookups = raw
+        .address_table_lookups
+        .iter()
+        .flatten()
+        .map(|lookup| {
+            Ok(MessageAddressTableLookup {
+                account_key: parse_pubkey(&lookup.account_key)?,
+                writable_indexes: lookup.writable_indexes.clone(),
+                readonly_indexes: lookup.readonly_indexes.clone(),
+            })
+        })
+        .collect::<Result<Vec<_>, DecodeError>>()?;
+
+    let instructions = raw
+        .instructions
+        .iter()
+        .enumerate()
+        .map(|(index, instruction)| {
+            Ok(CompiledInstruction {
+                path: vec![index],
+                program_id_index: instruction.program_id_index,
+                accounts: instruction.accounts.clone(),
+                data: decode_base58(&instruction.data)?,
+            })
+        })
+        .collect::<Result<Vec<_>, DecodeError>>()?;
+
+    Ok(TransactionMessage {
+        header: raw.header,
+        account_keys,
+        address_table_lookups,
+        instructions,
+    })
+}
+
+/// Static account keys followed by the addresses loaded from lookup tables,
+/// writable before readonly, as the runtime orders them.
+fn load_accounts(
+    message: &TransactionMessage,
+    meta: Option<&UiTransactionStatusMeta>,
+    resolver: Option<&dyn LookupTableResolver>,
+) -> Result<MessageAccounts, DecodeError> {
+    let mut accounts = MessageAccounts {
+        header: message.header,
+        static_keys: message.account_keys.clone(),
+        ..Default::default()
+    };
+    let Some(first_lookup) = message.address_table_lookups.first() else {
+        return Ok(accounts);
+    };
+
+    if let Some(OptionSerializer::Some(loaded)) = meta.map(|meta| &meta.loaded_addresses) {
+        accounts.loaded_writable = loaded
+            .writable
+            .iter()
+            .map(|key| parse_pubkey(key))
+            .collect::<Result<_, _>>()?;
+        accounts.loaded_readonly = loaded
+            .readonly
+            .iter()
+            .map(|key| parse_pubkey(key))
+            .collect::<Result<_, _>>()?;
+    } else if let Some(resolver) = resolver {
+        (accounts.loaded_writable, accounts.loaded_readonly) =
+            resolve_lookups(&message.address_table_lookups, resolver)?;
+    } else {
+        return Err(DecodeError::MissingLookupTable(first_lookup.account_key));
+    }
+
+    Ok(accounts)
+}
+
+/// Places each outer instruction's CPIs right after it, in execution order.
+fn nest_inner_instructions(
+    outer: Vec<CompiledInstruction>,
+    meta: &UiTransactionStatusMeta,
+) -> Result<Vec<CompiledInstruction>, DecodeError> {
+    let OptionSerializer::Some(inner_instructions) = &meta.inner_instructions else {
+        return Ok(outer);
+    };
+
+    let mut instructions = Vec::with_capacity(outer.len());
+    for instruction in outer {
+        let outer_index = instruction.path[0];
+        instructions.push(instruction);
+
+        for inner in inner_instructions
+            .iter()
+            .filter(|inner| inner.index as usize == outer_index)
+        {
+            let mut last_path = vec![outer_index];
+            for ui_instruction in &inner.instructions {
+                let UiInstruction::Compiled(compiled) = ui_instruction else {
+                    return Err(DecodeError::UnsupportedEncoding);
+                };
+
+                let path = next_path(&last_path, compiled.stack_height);
+                last_path.clone_from(&path);
+                instructions.push(CompiledInstruction {
+                    path,
+                    program_id_index: compiled.program_id_index,
+                    accounts: compiled.accounts.clone(),
+                    data: decode_base58(&compiled.data)?,
+                });
+            }
+        }
+    }
+
+    Ok(instructions)
+}
+
+/// Path of an inner instruction given the path of the one executed before
+/// it. Outer instructions run at stack height 1; nodes that predate stack
+/// heights are treated as direct CPIs of the outer instruction.
+fn next_path(last_path: &[usize], stack_height: Option<u32>) -> Vec<usize> {
+    let depth = (stack_height.unwrap_or(2).max(2) as usize).min(last_path.len() + 1);
+    let mut path = last_path[..depth - 1].to_vec();
+    let child_index = match last_path.get(depth - 1) {
+        Some(previous_sibling) => previous_sibling + 1,
+        None => 0,
+    };
+    path.push(child_index);
+    path
+}
+
+fn parse_pubkey(key: &str) -> Result<Pubkey, DecodeError> {
+    Pubkey::from_str(key).map_err(|_| DecodeError::InvalidTransaction(format!("bad pubkey {key}")))
+}
+
+fn decode_base58(data: &str) -> Result<Vec<u8>, DecodeError> {
+    bs58::decode(data)
+        .into_vec()
+        .map_err(|_| DecodeError::InvalidTransaction("bad base58 instruction data".to_string()))
+}
@@ -0,0 +1,284 @@
+#![allow(dead_code)]
+
+use serde_json::{json, Value};
+use solana_sdk::bs58;
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+
+pub const TOKEN_PROGRAM: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+const TRANSFER: u8 = 3;
+
+/// A confirmed transaction as `getTransaction` returns it in JSON encoding,
+/// built up from its account keys, instructions and meta. Account indexes
+/// count the static keys first and then the loaded addresses.
+pub struct TransactionBuilder {
+    keys: Vec<Pubkey>,
+    readonly_unsigned: usize,
+    instructions: Vec<Value>,
+    inner_instructions: Option<Vec<Value>>,
+    fee: u64,
+    err: Value,
+    pre_balances: Vec<u64>,
+    post_balances: Vec<u64>,
+    pre_token_balances: Vec<Value>,
+    post_token_balances: Vec<Value>,
+    lookups: Vec<Value>,
+    loaded_writable: Vec<Pubkey>,
+    loaded_readonly: Vec<Pubkey>,
+}
+
+impl TransactionBuilder {
+    /// A transaction signed by the first of `keys` alone, with every other
+    /// key writable.
+    pub fn new(keys: Vec<Pubkey>) -> Self {
+        TransactionBuilder {
+            keys,
+            readonly_unsigned: 0,
+            instructions: Vec::new(),
+            inner_instructions: Some(Vec::new()),
+            fee: 5_000,
+            err: Value::Null,
+            pre_balances: Vec::new(),
+            post_balances: Vec::new(),
+            pre_token_balances: Vec::new(),
+            post_token_balances: Vec::new(),
+            lookups: Vec::new(),
+            loaded_writable: Vec::new(),
+            loaded_readonly: Vec::new(),
+        }
+    }
+
+    /// Appends a static key.
+    pub fn account(mut self, key: Pubkey) -> Self {
+        self.keys.push(key);
+        self
+    }
+
+    /// Marks the last `count` static keys readonly.
+    pub fn readonly_unsigned(mut self, count: usize) -> Self {
+        self.readonly_unsigned = count;
+        self
+    }
+
+    /// Appends an outer instruction.
+    pub fn instruction(mut self, instruction: Value) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    /// Records the CPIs made by outer instruction `index`.
+    pub fn inner(mut self, index: usize, instructions: Vec<Value>) -> Self {
+        self.inner_instructions
+            .get_or_insert_with(Vec::new)
+            .push(json!({ "index": index, "instructions": instructions }));
+        self
+    }
+
+    /// Drops the inner instructions from the meta, as nodes that do not
+    /// record CPIs serve it.
+    pub fn without_inner_instructions(mut self) -> Self {
+        self.inner_instructions = None;
+        self
+    }
+
+    pub fn fee(mut self, fee: u64) -> Self {
+        self.fee = fee;
+        self
+    }
+
+    /// Marks the transaction failed with `err`, as the runtime reports it.
+    pub fn err(mut self, err: Value) -> Self {
+        self.err = err;
+        self
+    }
+
+    /// Lamports of account `index` before and after the transaction.
+    pub fn lamports(mut self, index: usize, pre: u64, post: u64) -> Self {
+        let len = self.account_count().max(index + 1);
+        self.pre_balances.resize(len, 0);
+        self.post_balances.resize(len, 0);
+        self.pre_balances[index] = pre;
+        self.post_balances[index] = post;
+        self
+    }
+
+    /// Token balance of account `index` before and after the transaction. A
+    /// side that is `None` is left out, as for accounts created or closed by
+    /// the transaction.
+    pub fn token_balance(
+        mut self,
+        index: u8,
+        mint: Pubkey,
+        owner: Pubkey,
+        pre: Option<u64>,
+        post: Option<u64>,
+    ) -> Self {
+        if let Some(amount) = pre {
+            self.pre_token_balances
+                .push(token_balance(index, mint, owner, amount));
+        }
+        if let Some(amount) = post {
+            self.post_token_balances
+                .push(token_balance(index, mint, owner, amount));
+        }
+        self
+    }
+
+    /// Loads `writable` and `readonly`, each key paired with its position in
+    /// `table`, and lists them in the meta, making this a v0 transaction.
+    pub fn lookup(
+        mut self,
+        table: Pubkey,
+        writable: &[(u8, Pubkey)],
+        readonly: &[(u8, Pubkey)],
+    ) -> Self {
+        self.lookups.push(json!({
+            "accountKey": table.to_string(),
+            "writableIndexes": writable.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+            "readonlyIndexes": readonly.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+        }));
+        self.loaded_writable
+            .extend(writable.iter().map(|(_, key)| *key));
+        self.loaded_readonly
+            .extend(readonly.iter().map(|(_, key)| *key));
+        self
+    }
+
+    fn account_count(&self) -> usize {
+        self.keys.len() + self.loaded_writable.len() + self.loaded_readonly.len()
+    }
+
+    pub fn json(&self) -> Value {
+        // Lamports are listed for every account once any are set.
+        let account_count = self.account_count();
+        let balances = |balances: &[u64]| {
+            let mut balances = balances.to_vec();
+            if !balances.is_empty() {
+                balances.resize(account_count, 0);
+            }
+            balances
+        };
+        let mut message = json!({
+            "header": {
+                "numRequiredSignatures": 1,
+                "numReadonlySignedAccounts": 0,
+                "numReadonlyUnsignedAccounts": self.readonly_unsigned,
+            },
+            "accountKeys": strings(&self.keys),
+            "recentBlockhash": "11111111111111111111111111111111",
+            "instructions": self.instructions,
+        });
+        let status = if self.err.is_null() {
+            json!({ "Ok": null })
+        } else {
+            json!({ "Err": self.err })
+        };
+        let mut transaction = json!({
+            "slot": 1,
+            "blockTime": null,
+            "transaction": {
+                "signatures": ["1111111111111111111111111111111111111111111111111111111111111111"],
+                "message": null,
+            },
+            "meta": {
+                "err": self.err,
+                "status": status,
+                "fee": self.fee,
+                "preBalances": balances(&self.pre_balances),
+                "postBalances": balances(&self.post_balances),
+                "innerInstructions": self.inner_instructions,
+                "preTokenBalances": self.pre_token_balances,
+                "postTokenBalances": self.post_token_balances,
+            },
+        });
+        if !self.lookups.is_empty() {
+            message["addressTableLookups"] = json!(self.lookups);
+            transaction["version"] = json!(0);
+            transaction["meta"]["loadedAddresses"] = json!({
+                "writable": strings(&self.loaded_writable),
+                "readonly": strings(&self.loaded_readonly),
+            });
+        }
+        transaction["transaction"]["message"] = message;
+        transaction
+    }
+
+    pub fn build(&self) -> EncodedConfirmedTransactionWithStatusMeta {
+        parse(self.json())
+    }
+}
+
+pub fn parse(transaction: Value) -> EncodedConfirmedTransactionWithStatusMeta {
+    serde_json::from_value(transaction).unwrap()
+}
+
+/// A compiled instruction; `stack_height` is `None` for outer instructions
+/// and for CPIs recorded before stack heights were.
+pub fn instruction(
+    program_index: u8,
+    accounts: &[u8],
+    data: &[u8],
+    stack_height: Option<u32>,
+) -> Value {
+    json!({
+        "programIdIndex": program_index,
+        "accounts": accounts,
+        "data": bs58::encode(data).into_string(),
+        "stackHeight": stack_height,
+    })
+}
+
+/// An SPL Token `Transfer` CPI, signed by `authority`.
+pub fn token_transfer(
+    token_program_index: u8,
+    source: u8,
+    destination: u8,
+    authority: u8,
+    amount: u64,
+    stack_height: u32,
+) -> Value {
+    let mut data = vec![TRANSFER];
+    data.extend(amount.to_le_bytes());
+    instruction(
+        token_program_index,
+        &[source, destination, authority],
+        &data,
+        Some(stack_height),
+    )
+}
+
+/// `count` distinct account keys.
+pub fn unique_keys(count: usize) -> Vec<Pubkey> {
+    (0..count).map(|_| Pubkey::new_unique()).collect()
+}
+
+/// Instruction data: `discriminator` followed by `amounts` as little-endian
+/// `u64`s.
+pub fn instruction_data(discriminator: &[u8], amounts: &[u64]) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    for amount in amounts {
+        data.extend(amount.to_le_bytes());
+    }
+    data
+}
+
+fn token_balance(index: u8, mint: Pubkey, owner: Pubkey, amount: u64) -> Value {
+    json!({
+        "accountIndex": index,
+        "mint": mint.to_string(),
+        "owner": owner.to_string(),
+        "programId": TOKEN_PROGRAM.to_string(),
+        "uiTokenAmount": {
+            "amount": amount.to_string(),
+            "decimals": 9,
+            "uiAmount": null,
+            "uiAmountString": "0",
+        },
+    })
+}
+
+fn strings(keys: &[Pubkey]) -> Vec<String> {
+    keys.iter().map(Pubkey::to_string).collect()
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use carbon_okx_dex_decoder::PROGRAM_ID as OKX;
+use common::{instruction_data, unique_keys};
+use solana_sdk::pubkey::Pubkey;
+use teste::{decode_instruction, DecodeError, Protocol, SwapMode, PROTOCOLS};
+
+const SWAP_BASE_IN: [u8; 1] = [9];
+const BUY: [u8; 8] = [0x66, 0x06, 0x3d, 0x12, 0x01, 0xda, 0xeb, 0xea];
+const ROUTE: [u8; 8] = [0xe5, 0x17, 0xcb, 0x97, 0x7a, 0xe3, 0xad, 0x2a];
+const OKX_SWAP: [u8; 8] = [0xf8, 0xc6, 0x9e, 0x91, 0xe1, 0x75, 0x87, 0xc8];
+const BUY_EXACT_IN: [u8; 8] = [0xfa, 0xea, 0x0d, 0x7b, 0xd5, 0x9c, 0x13, 0xec];
+
+/// Every registered program, the OKX router included.
+fn programs() -> impl Iterator<Item = (Pubkey, Protocol)> {
+    PROTOCOLS.iter().copied().chain([(OKX, Protocol::Okx)])
+}
+
+/// Data and account count of a swap the protocol's decoder accepts, with the
+/// mode the swap decodes to.
+fn valid_swap(protocol: Protocol) -> (Vec<u8>, usize, SwapMode) {
+    match protocol {
+        Protocol::RaydiumAmmV4 => (
+            instruction_data(&SWAP_BASE_IN, &[1_000, 900]),
+            17,
+            SwapMode::ExactIn,
+        ),
+        Protocol::Pumpfun => (
+            instruction_data(&BUY, &[1_000, 2_000_000]),
+            12,
+            SwapMode::ExactOut,
+        ),
+        Protocol::PumpSwap => (
+            instruction_data(&BUY, &[1_000, 2_000_000]),
+            19,
+            SwapMode::ExactOut,
+        ),
+        Protocol::Jupiter => {
+            // An empty route plan, then the amounts, slippage and platform fee.
+            let mut data = instruction_data(&ROUTE, &[]);
+            data.extend(0u32.to_le_bytes());
+            data.extend(1_000u64.to_le_bytes());
+            data.extend(990u64.to_le_bytes());
+            data.extend(50u16.to_le_bytes());
+            data.push(0);
+            (data, 9, SwapMode::ExactIn)
+        }
+        Protocol::Okx => {
+            // The whole input through a single Whirlpool hop.
+            let mut data = instruction_data(&OKX_SWAP, &[1_000, 990, 980]);
+            data.extend(1u32.to_le_bytes());
+            data.extend(1_000u64.to_le_bytes());
+            data.extend([1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 2, 1, 0, 0, 0, 100]);
+            (data, 5, SwapMode::ExactIn)
+        }
+        Protocol::RaydiumLaunchpad => (
+            instruction_data(&BUY_EXACT_IN, &[1_000, 900, 0]),
+            15,
+            SwapMode::ExactIn,
+        ),
+    }
+}
+
+#[test]
+fn unregistered_program_is_unsupported() {
+    let program_id = Pubkey::new_unique();
+
+    let error = decode_instruction(vec![9], unique_keys(17), program_id).unwrap_err();
+
+    assert_eq!(error, DecodeError::UnsupportedProgram(program_id));
+}
+
+#[test]
+fn junk_data_is_an_unknown_instruction_of_the_program() {
+    for (program_id, protocol) in programs() {
+        let error = decode_instruction(vec![0xff; 8], unique_keys(20), program_id).unwrap_err();
+
+        assert_eq!(
+            error,
+            DecodeError::UnknownDiscriminator { protocol },
+            "{protocol:?}"
+        );
+    }
+}
+
+#[test]
+fn every_program_dispatches_to_its_decoder() {
+    for (program_id, protocol) in programs() {
+        assert_eq!(Protocol::from_program_id(&program_id), Some(protocol));
+        assert_eq!(protocol.program_id(), program_id);
+
+        let (data, account_count, swap_mode) = valid_swap(protocol);
+        let swap = decode_instruction(data, unique_keys(account_count), program_id)
+            .unwrap_or_else(|error| panic!("{protocol:?}: {error}"));
+
+        assert_eq!(swap.swap_mode, Some(swap_mode), "{protocol:?}");
+    }
+}
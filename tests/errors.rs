@@ -0,0 +1,151 @@
+mod common;
+
+use carbon_jupiter_swap_decoder::PROGRAM_ID as JUPITER;
+use carbon_okx_dex_decoder::PROGRAM_ID as OKX;
+use carbon_pump_swap_decoder::PROGRAM_ID as PUMP_SWAP;
+use carbon_pumpfun_decoder::PROGRAM_ID as PUMPFUN;
+use carbon_raydium_amm_v4_decoder::PROGRAM_ID as RAYDIUM_AMM_V4;
+use carbon_raydium_launchpad_decoder::PROGRAM_ID as LAUNCHPAD;
+use common::{instruction_data, unique_keys};
+use teste::{
+    decode_jupiter_instruction, decode_okx_instruction, decode_pumpfun_instruction,
+    decode_pumpswap_instruction, decode_raydium_instruction, decode_raydiumlaunchpad_instruction,
+    DecodeError, Protocol,
+};
+
+const SWAP_BASE_IN: [u8; 1] = [9];
+const BUY: [u8; 8] = [0x66, 0x06, 0x3d, 0x12, 0x01, 0xda, 0xeb, 0xea];
+const ROUTE: [u8; 8] = [0xe5, 0x17, 0xcb, 0x97, 0x7a, 0xe3, 0xad, 0x2a];
+const OKX_SWAP: [u8; 8] = [0xf8, 0xc6, 0x9e, 0x91, 0xe1, 0x75, 0x87, 0xc8];
+const BUY_EXACT_IN: [u8; 8] = [0xfa, 0xea, 0x0d, 0x7b, 0xd5, 0x9c, 0x13, 0xec];
+
+fn not_enough_accounts(instruction: &str, expected: usize, actual: usize) -> DecodeError {
+    DecodeError::NotEnoughAccounts {
+        instruction: instruction.to_string(),
+        expected,
+        actual,
+    }
+}
+
+#[test]
+fn wrong_program() {
+    let error = decode_raydium_instruction(
+        instruction_data(&SWAP_BASE_IN, &[1_000, 900]),
+        unique_keys(17),
+        PUMPFUN,
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        error,
+        DecodeError::WrongProgram {
+            expected: RAYDIUM_AMM_V4,
+            actual: PUMPFUN
+        }
+    );
+}
+
+/// A known discriminator followed by a payload too short for its arguments.
+#[test]
+fn broken_payload_after_a_known_discriminator() {
+    let error =
+        decode_pumpfun_instruction(instruction_data(&BUY, &[1_000]), unique_keys(12), PUMPFUN)
+            .unwrap_err();
+
+    assert_eq!(
+        error,
+        DecodeError::Deserialize {
+            protocol: Protocol::Pumpfun,
+            instruction: "Buy".to_string()
+        }
+    );
+}
+
+#[test]
+fn raydium_with_too_few_accounts() {
+    let error = decode_raydium_instruction(
+        instruction_data(&SWAP_BASE_IN, &[1_000, 900]),
+        unique_keys(16),
+        RAYDIUM_AMM_V4,
+    )
+    .unwrap_err();
+
+    assert_eq!(error, not_enough_accounts("SwapBaseIn", 17, 16));
+}
+
+/// `SwapBaseIn` only takes 17 or 18 accounts, so a longer list is reported
+/// against the smallest count it accepts.
+#[test]
+fn raydium_with_too_many_accounts() {
+    let error = decode_raydium_instruction(
+        instruction_data(&SWAP_BASE_IN, &[1_000, 900]),
+        unique_keys(19),
+        RAYDIUM_AMM_V4,
+    )
+    .unwrap_err();
+
+    assert_eq!(error, not_enough_accounts("SwapBaseIn", 17, 19));
+}
+
+#[test]
+fn pumpfun_with_too_few_accounts() {
+    let error = decode_pumpfun_instruction(
+        instruction_data(&BUY, &[1_000, 2_000_000]),
+        unique_keys(11),
+        PUMPFUN,
+    )
+    .unwrap_err();
+
+    assert_eq!(error, not_enough_accounts("Buy", 12, 11));
+}
+
+#[test]
+fn pump_swap_with_too_few_accounts() {
+    let error = decode_pumpswap_instruction(
+        instruction_data(&BUY, &[1_000, 2_000_000]),
+        unique_keys(18),
+        PUMP_SWAP,
+    )
+    .unwrap_err();
+
+    assert_eq!(error, not_enough_accounts("Buy", 19, 18));
+}
+
+#[test]
+fn jupiter_with_too_few_accounts() {
+    // An empty route plan, then the amounts, slippage and platform fee.
+    let mut data = instruction_data(&ROUTE, &[]);
+    data.extend(0u32.to_le_bytes());
+    data.extend(1_000u64.to_le_bytes());
+    data.extend(990u64.to_le_bytes());
+    data.extend(50u16.to_le_bytes());
+    data.push(0);
+
+    let error = decode_jupiter_instruction(data, unique_keys(8), JUPITER).unwrap_err();
+
+    assert_eq!(error, not_enough_accounts("Route", 9, 8));
+}
+
+#[test]
+fn okx_with_too_few_accounts() {
+    // No amounts and no routes.
+    let mut data = instruction_data(&OKX_SWAP, &[1_000, 990, 980]);
+    data.extend(0u32.to_le_bytes());
+    data.extend(0u32.to_le_bytes());
+
+    let error = decode_okx_instruction(data, unique_keys(4), OKX).unwrap_err();
+
+    assert_eq!(error, not_enough_accounts("Swap", 5, 4));
+}
+
+#[test]
+fn launchpad_with_too_few_accounts() {
+    let error = decode_raydiumlaunchpad_instruction(
+        instruction_data(&BUY_EXACT_IN, &[1_000, 900, 0]),
+        unique_keys(14),
+        LAUNCHPAD,
+    )
+    .unwrap_err();
+
+    assert_eq!(error, not_enough_accounts("BuyExactIn", 15, 14));
+}
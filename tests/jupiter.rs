@@ -0,0 +1,798 @@
+mod common;
+
+use carbon_jupiter_swap_decoder::types::Swap;
+use carbon_jupiter_swap_decoder::PROGRAM_ID as JUPITER;
+use common::{instruction, parse, TransactionBuilder, TOKEN_PROGRAM};
+use serde_json::Value;
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use teste::{decode_instruction, decode_transaction, FeeSide, SwapMode};
+
+const USDC: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+const WSOL: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+const TOKEN_2022_PROGRAM: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+const ROUTE: [u8; 8] = [0xe5, 0x17, 0xcb, 0x97, 0x7a, 0xe3, 0xad, 0x2a];
+const EXACT_OUT_ROUTE: [u8; 8] = [0xd0, 0x33, 0xef, 0x97, 0x7b, 0x2b, 0xed, 0x5c];
+const SHARED_ACCOUNTS_ROUTE: [u8; 8] = [0xc1, 0x20, 0x9b, 0x33, 0x41, 0xd6, 0x9c, 0x81];
+const SHARED_ACCOUNTS_EXACT_OUT_ROUTE: [u8; 8] = [0xb0, 0xd1, 0x69, 0xa8, 0x9a, 0x7d, 0x45, 0x3e];
+const ROUTE_WITH_TOKEN_LEDGER: [u8; 8] = [0x96, 0x56, 0x47, 0x74, 0xa7, 0x5d, 0x0e, 0x68];
+const SHARED_ACCOUNTS_ROUTE_WITH_TOKEN_LEDGER: [u8; 8] =
+    [0xe6, 0x79, 0x8f, 0x50, 0x77, 0x9f, 0x6a, 0xaa];
+const SWAP_EVENT: [u8; 16] = [
+    0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d, 0x40, 0xc6, 0xcd, 0xe8, 0x26, 0x08, 0x71, 0xe2,
+];
+const FEE_EVENT: [u8; 16] = [
+    0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d, 0x49, 0x4f, 0x4e, 0x7f, 0xb8, 0xd5, 0x0d, 0xdc,
+];
+
+/// Accounts of a route without shared accounts, in instruction order.
+struct RouteAccounts {
+    user: Pubkey,
+    user_source: Pubkey,
+    user_destination: Pubkey,
+    destination: Pubkey,
+    platform_fee: Pubkey,
+}
+
+impl RouteAccounts {
+    fn new() -> Self {
+        RouteAccounts {
+            user: Pubkey::new_unique(),
+            user_source: Pubkey::new_unique(),
+            user_destination: Pubkey::new_unique(),
+            // Jupiter passes its own ID for optional accounts left unset.
+            destination: JUPITER,
+            platform_fee: JUPITER,
+        }
+    }
+}
+
+/// Accounts of a shared-accounts route, in instruction order.
+struct SharedAccounts {
+    program_authority: Pubkey,
+    user: Pubkey,
+    source: Pubkey,
+    program_source: Pubkey,
+    program_destination: Pubkey,
+    destination: Pubkey,
+}
+
+impl SharedAccounts {
+    fn new() -> Self {
+        SharedAccounts {
+            program_authority: Pubkey::new_unique(),
+            user: Pubkey::new_unique(),
+            source: Pubkey::new_unique(),
+            program_source: Pubkey::new_unique(),
+            program_destination: Pubkey::new_unique(),
+            destination: Pubkey::new_unique(),
+        }
+    }
+
+    fn keys(&self, token_ledger: Option<Pubkey>) -> Vec<Pubkey> {
+        let mut keys = vec![
+            TOKEN_PROGRAM,
+            self.program_authority,
+            self.user,
+            self.source,
+            self.program_source,
+            self.program_destination,
+            self.destination,
+            WSOL,
+            USDC,
+            JUPITER,
+            TOKEN_2022_PROGRAM,
+        ];
+        keys.extend(token_ledger);
+        keys.extend([Pubkey::new_unique(), JUPITER]);
+        keys
+    }
+}
+
+/// WSOL to USDC through a Whirlpool, then a Raydium pool.
+fn route_plan(data: &mut Vec<u8>) {
+    data.extend(2u32.to_le_bytes());
+    data.extend([17, 1, 100, 0, 1]);
+    data.extend([7, 100, 1, 2]);
+}
+
+fn assert_route_plan(swap: &teste::SwapTransaction) {
+    let hops: Vec<_> = swap
+        .jupiter_hops
+        .iter()
+        .map(|hop| (hop.swap.clone(), hop.input_index, hop.output_index))
+        .collect();
+    assert_eq!(
+        hops,
+        vec![
+            (Swap::Whirlpool { a_to_b: true }, 0, 1),
+            (Swap::Raydium, 1, 2)
+        ]
+    );
+}
+
+#[test]
+fn route() {
+    let accounts = RouteAccounts::new();
+    let mut data = ROUTE.to_vec();
+    route_plan(&mut data);
+    data.extend(1_000_000_000u64.to_le_bytes());
+    data.extend(150_000_000u64.to_le_bytes());
+    data.extend(50u16.to_le_bytes());
+    data.push(0);
+    let keys = vec![
+        TOKEN_PROGRAM,
+        accounts.user,
+        accounts.user_source,
+        accounts.user_destination,
+        accounts.destination,
+        USDC,
+        accounts.platform_fee,
+        Pubkey::new_unique(),
+        JUPITER,
+    ];
+
+    let swap = decode_instruction(data, keys, JUPITER).unwrap();
+
+    assert_eq!(swap.swap_mode, Some(SwapMode::ExactIn));
+    assert_eq!(swap.amount_in, Some(1_000_000_000));
+    assert_eq!(swap.quoted_amount_out, Some(150_000_000));
+    assert_eq!(swap.min_amount_out, Some(149_250_000));
+    // The instruction does not name its source mint.
+    assert_eq!(swap.mint_token_in, None);
+    assert_eq!(swap.mint_token_out, Some(USDC));
+    assert_eq!(swap.mint_token_account_in, Some(accounts.user_source));
+    assert_eq!(swap.mint_token_account_out, Some(accounts.user_destination));
+    assert_eq!(swap.user, Some(accounts.user));
+    assert_route_plan(&swap);
+}
+
+#[test]
+fn route_to_explicit_destination() {
+    let mut accounts = RouteAccounts::new();
+    accounts.destination = Pubkey::new_unique();
+    let mut data = ROUTE.to_vec();
+    route_plan(&mut data);
+    data.extend(1_000u64.to_le_bytes());
+    data.extend(150u64.to_le_bytes());
+    data.extend(0u16.to_le_bytes());
+    data.push(0);
+    let keys = vec![
+        TOKEN_PROGRAM,
+        accounts.user,
+        accounts.user_source,
+        accounts.user_destination,
+        accounts.destination,
+        USDC,
+        accounts.platform_fee,
+        Pubkey::new_unique(),
+        JUPITER,
+    ];
+
+    let swap = decode_instruction(data, keys, JUPITER).unwrap();
+
+    assert_eq!(swap.mint_token_account_out, Some(accounts.destination));
+}
+
+#[test]
+fn exact_out_route() {
+    let accounts = RouteAccounts::new();
+    let mut data = EXACT_OUT_ROUTE.to_vec();
+    route_plan(&mut data);
+    data.extend(150_000_000u64.to_le_bytes());
+    data.extend(1_000_000_000u64.to_le_bytes());
+    data.extend(100u16.to_le_bytes());
+    data.push(0);
+    let keys = vec![
+        TOKEN_PROGRAM,
+        accounts.user,
+        accounts.user_source,
+        accounts.user_destination,
+        accounts.destination,
+        WSOL,
+        USDC,
+        accounts.platform_fee,
+        TOKEN_2022_PROGRAM,
+        Pubkey::new_unique(),
+        JUPITER,
+    ];
+
+    let swap = decode_instruction(data, keys, JUPITER).unwrap();
+
+    assert_eq!(swap.swap_mode, Some(SwapMode::ExactOut));
+    assert_eq!(swap.amount_out, Some(150_000_000));
+    assert_eq!(swap.quoted_amount_in, Some(1_000_000_000));
+    assert_eq!(swap.max_amount_in, Some(1_010_000_000));
+    assert_eq!(swap.mint_token_in, Some(WSOL));
+    assert_eq!(swap.mint_token_out, Some(USDC));
+    assert_eq!(swap.mint_token_account_in, Some(accounts.user_source));
+    assert_eq!(swap.mint_token_account_out, Some(accounts.user_destination));
+    assert_eq!(swap.user, Some(accounts.user));
+    assert_route_plan(&swap);
+}
+
+#[test]
+fn shared_accounts_route() {
+    let accounts = SharedAccounts::new();
+    let mut data = SHARED_ACCOUNTS_ROUTE.to_vec();
+    data.push(3);
+    route_plan(&mut data);
+    data.extend(1_000_000_000u64.to_le_bytes());
+    data.extend(150_000_000u64.to_le_bytes());
+    data.extend(50u16.to_le_bytes());
+    data.push(0);
+
+    let swap = decode_instruction(data, accounts.keys(None), JUPITER).unwrap();
+
+    assert_eq!(swap.swap_mode, Some(SwapMode::ExactIn));
+    assert_eq!(swap.amount_in, Some(1_000_000_000));
+    assert_eq!(swap.min_amount_out, Some(149_250_000));
+    assert_eq!(swap.mint_token_in, Some(WSOL));
+    assert_eq!(swap.mint_token_out, Some(USDC));
+    assert_eq!(swap.mint_token_account_in, Some(accounts.source));
+    assert_eq!(swap.mint_token_account_out, Some(accounts.destination));
+    assert_eq!(swap.user, Some(accounts.user));
+    assert_route_plan(&swap);
+}
+
+#[test]
+fn shared_accounts_exact_out_route() {
+    let accounts = SharedAccounts::new();
+    let mut data = SHARED_ACCOUNTS_EXACT_OUT_ROUTE.to_vec();
+    data.push(3);
+    route_plan(&mut data);
+    data.extend(150_000_000u64.to_le_bytes());
+    data.extend(1_000_000_000u64.to_le_bytes());
+    data.extend(100u16.to_le_bytes());
+    data.push(0);
+
+    let swap = decode_instruction(data, accounts.keys(None), JUPITER).unwrap();
+
+    assert_eq!(swap.swap_mode, Some(SwapMode::ExactOut));
+    assert_eq!(swap.amount_out, Some(150_000_000));
+    assert_eq!(swap.max_amount_in, Some(1_010_000_000));
+    assert_eq!(swap.mint_token_in, Some(WSOL));
+    assert_eq!(swap.mint_token_out, Some(USDC));
+    assert_eq!(swap.mint_token_account_in, Some(accounts.source));
+    assert_eq!(swap.mint_token_account_out, Some(accounts.destination));
+    assert_eq!(swap.user, Some(accounts.user));
+    assert_route_plan(&swap);
+}
+
+#[test]
+fn route_with_token_ledger() {
+    let accounts = RouteAccounts::new();
+    let token_ledger = Pubkey::new_unique();
+    let mut data = ROUTE_WITH_TOKEN_LEDGER.to_vec();
+    route_plan(&mut data);
+    data.extend(150_000_000u64.to_le_bytes());
+    data.extend(50u16.to_le_bytes());
+    data.push(0);
+    let keys = vec![
+        TOKEN_PROGRAM,
+        accounts.user,
+        accounts.user_source,
+        accounts.user_destination,
+        accounts.destination,
+        USDC,
+        accounts.platform_fee,
+        token_ledger,
+        Pubkey::new_unique(),
+        JUPITER,
+    ];
+
+    let swap = decode_instruction(data, keys, JUPITER).unwrap();
+
+    assert_eq!(swap.swap_mode, Some(SwapMode::ExactIn));
+    assert_eq!(swap.amount_in, None);
+    assert_eq!(swap.min_amount_out, Some(149_250_000));
+    assert_eq!(swap.mint_token_out, Some(USDC));
+    assert_eq!(swap.mint_token_account_in, Some(accounts.user_source));
+    assert_eq!(swap.mint_token_account_out, Some(accounts.user_destination));
+    assert_eq!(swap.token_ledger, Some(token_ledger));
+    assert_route_plan(&swap);
+}
+
+#[test]
+fn shared_accounts_route_with_token_ledger() {
+    let accounts = SharedAccounts::new();
+    let token_ledger = Pubkey::new_unique();
+    let mut data = SHARED_ACCOUNTS_ROUTE_WITH_TOKEN_LEDGER.to_vec();
+    data.push(3);
+    route_plan(&mut data);
+    data.extend(150_000_000u64.to_le_bytes());
+    data.extend(50u16.to_le_bytes());
+    data.push(0);
+
+    let swap = decode_instruction(data, accounts.keys(Some(token_ledger)), JUPITER).unwrap();
+
+    assert_eq!(swap.amount_in, None);
+    assert_eq!(swap.mint_token_in, Some(WSOL));
+    assert_eq!(swap.mint_token_out, Some(USDC));
+    assert_eq!(swap.mint_token_account_in, Some(accounts.source));
+    assert_eq!(swap.mint_token_account_out, Some(accounts.destination));
+    assert_eq!(swap.token_ledger, Some(token_ledger));
+}
+
+fn swap_event_data(
+    amm: Pubkey,
+    input_mint: Pubkey,
+    input_amount: u64,
+    output_mint: Pubkey,
+    output_amount: u64,
+) -> Vec<u8> {
+    let mut data = SWAP_EVENT.to_vec();
+    data.extend(amm.to_bytes());
+    data.extend(input_mint.to_bytes());
+    data.extend(input_amount.to_le_bytes());
+    data.extend(output_mint.to_bytes());
+    data.extend(output_amount.to_le_bytes());
+    data
+}
+
+fn fee_event_data(account: Pubkey, mint: Pubkey, amount: u64) -> Vec<u8> {
+    let mut data = FEE_EVENT.to_vec();
+    data.extend(account.to_bytes());
+    data.extend(mint.to_bytes());
+    data.extend(amount.to_le_bytes());
+    data
+}
+
+#[test]
+fn swap_event() {
+    let data = swap_event_data(Pubkey::new_unique(), WSOL, 1_000_000_000, USDC, 150_123_456);
+
+    let swap = decode_instruction(data, vec![Pubkey::new_unique()], JUPITER).unwrap();
+
+    assert_eq!(swap.amount_in, Some(1_000_000_000));
+    assert_eq!(swap.amount_out, Some(150_123_456));
+    assert_eq!(swap.mint_token_in, Some(WSOL));
+    assert_eq!(swap.mint_token_out, Some(USDC));
+}
+
+/// A confirmed transaction holding one WSOL to USDC `Route`, which made
+/// the CPIs in `inner`. A platform fee account, when set, charges 20 bps and
+/// receives 300_000.
+fn route_transaction(accounts: &RouteAccounts, inner: Vec<Value>) -> TransactionBuilder {
+    let charges_fee = accounts.platform_fee != JUPITER;
+    let mut data = ROUTE.to_vec();
+    route_plan(&mut data);
+    data.extend(1_000_000_000u64.to_le_bytes());
+    data.extend(150_000_000u64.to_le_bytes());
+    data.extend(50u16.to_le_bytes());
+    data.push(if charges_fee { 20 } else { 0 });
+
+    let mut keys = vec![
+        accounts.user,
+        accounts.user_source,
+        accounts.user_destination,
+        TOKEN_PROGRAM,
+        JUPITER,
+        USDC,
+        Pubkey::new_unique(),
+    ];
+    if charges_fee {
+        keys.push(accounts.platform_fee);
+    }
+    let platform_fee_index = if charges_fee { 7 } else { 4 };
+    let mut transaction = TransactionBuilder::new(keys)
+        .readonly_unsigned(4)
+        .instruction(instruction(
+            4,
+            &[3, 0, 1, 2, 4, 5, platform_fee_index, 6, 4],
+            &data,
+            None,
+        ))
+        .inner(0, inner)
+        .token_balance(1, WSOL, accounts.user, Some(1_000_000_000), Some(0))
+        .token_balance(2, USDC, accounts.user, Some(0), Some(150_100_000));
+    if charges_fee {
+        transaction = transaction.token_balance(7, USDC, accounts.user, Some(0), Some(300_000));
+    }
+    transaction
+}
+
+/// Swap events, each emitted as a CPI back into Jupiter.
+fn event_cpis(events: &[Vec<u8>]) -> Vec<Value> {
+    events
+        .iter()
+        .map(|event| instruction(4, &[6], event, Some(2)))
+        .collect()
+}
+
+/// A `Route` has no source mint account; the transaction decoder reads it
+/// from the token balances of the source account.
+#[test]
+fn route_source_mint_from_token_balances() {
+    let accounts = RouteAccounts::new();
+
+    let decoded = decode_transaction(&route_transaction(&accounts, vec![]).build()).unwrap();
+
+    let swap = &decoded.swaps[0].swap;
+    assert_eq!(swap.mint_token_in, Some(WSOL));
+    assert_eq!(swap.mint_token_out, Some(USDC));
+    assert_eq!(swap.mint_token_account_out, Some(accounts.user_destination));
+    assert_eq!(swap.realized_in, Some(1_000_000_000));
+    assert_eq!(swap.realized_out, Some(150_100_000));
+}
+
+#[test]
+fn swap_events_fill_route_hops() {
+    let accounts = RouteAccounts::new();
+    let whirlpool = Pubkey::new_unique();
+    let raydium = Pubkey::new_unique();
+    let bonk = Pubkey::new_unique();
+    let events = [
+        swap_event_data(whirlpool, WSOL, 1_000_000_000, bonk, 7_000_000),
+        swap_event_data(raydium, bonk, 7_000_000, USDC, 150_100_000),
+    ];
+
+    let decoded =
+        decode_transaction(&route_transaction(&accounts, event_cpis(&events)).build()).unwrap();
+
+    assert_eq!(decoded.swaps.len(), 1);
+    let hops = &decoded.swaps[0].swap.jupiter_hops;
+    assert_eq!(hops[0].amm, Some(whirlpool));
+    assert_eq!(hops[0].input_mint, Some(WSOL));
+    assert_eq!(hops[0].output_mint, Some(bonk));
+    assert_eq!(hops[0].amount_in, Some(1_000_000_000));
+    assert_eq!(hops[0].amount_out, Some(7_000_000));
+    assert_eq!(hops[1].amm, Some(raydium));
+    assert_eq!(hops[1].input_mint, Some(bonk));
+    assert_eq!(hops[1].amount_out, Some(150_100_000));
+}
+
+/// A pool swap the route made is kept, but linked to the route so that the
+/// trade is not counted twice.
+#[test]
+fn pool_swaps_inside_a_route_are_linked_to_it() {
+    let accounts = RouteAccounts::new();
+    let mut swap_base_in = vec![9];
+    swap_base_in.extend(1_000_000_000u64.to_le_bytes());
+    swap_base_in.extend(150_000_000u64.to_le_bytes());
+    // Pool, market and vault accounts do not matter here; the user's token
+    // accounts and wallet come last.
+    let mut raydium_accounts = vec![3];
+    raydium_accounts.extend([6; 13]);
+    raydium_accounts.extend([1, 2, 0]);
+    let transaction = route_transaction(
+        &accounts,
+        vec![instruction(7, &raydium_accounts, &swap_base_in, Some(2))],
+    )
+    .account(carbon_raydium_amm_v4_decoder::PROGRAM_ID)
+    .build();
+
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    let swaps: Vec<_> = decoded
+        .swaps
+        .iter()
+        .map(|swap| (swap.program_id, swap.path.clone(), swap.parent_path.clone()))
+        .collect();
+    assert_eq!(
+        swaps,
+        vec![
+            (JUPITER, vec![0], None),
+            (
+                carbon_raydium_amm_v4_decoder::PROGRAM_ID,
+                vec![0, 0],
+                Some(vec![0])
+            ),
+        ]
+    );
+    assert_eq!(
+        decoded.swaps[1].swap.mint_token_account_in,
+        Some(accounts.user_source)
+    );
+}
+
+/// Events outside any route, e.g. from a transaction whose route failed to
+/// decode, are still reported on their own.
+#[test]
+fn orphan_swap_events_are_kept() {
+    let events = [swap_event_data(
+        Pubkey::new_unique(),
+        WSOL,
+        1_000,
+        USDC,
+        150,
+    )];
+    let mut transaction = route_transaction(&RouteAccounts::new(), event_cpis(&events)).json();
+    // Point the outer instruction at the token program so no route decodes.
+    transaction["transaction"]["message"]["instructions"][0]["programIdIndex"] = 3.into();
+    let transaction = parse(transaction);
+
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    assert_eq!(decoded.swaps.len(), 1);
+    assert_eq!(decoded.swaps[0].swap.amount_in, Some(1_000));
+    assert_eq!(decoded.swaps[0].swap.mint_token_out, Some(USDC));
+}
+
+#[test]
+fn route_platform_fee() {
+    let mut accounts = RouteAccounts::new();
+    accounts.platform_fee = Pubkey::new_unique();
+    let fee_event = fee_event_data(accounts.platform_fee, USDC, 300_000);
+
+    let transaction = route_transaction(&accounts, event_cpis(&[fee_event])).build();
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    assert_eq!(decoded.swaps.len(), 1);
+    assert!(decoded.failures.is_empty());
+
+    let commission = decoded.swaps[0].swap.commission.clone().unwrap();
+    assert_eq!(commission.recipient, accounts.platform_fee);
+    assert_eq!(commission.mint, USDC);
+    assert_eq!(commission.bps, 20);
+    assert_eq!(commission.side, FeeSide::Output);
+    assert_eq!(commission.amount, Some(300_000));
+}
+
+/// The fee event counts what this route paid, even when something else also
+/// paid the fee account in the same transaction.
+#[test]
+fn platform_fee_from_the_route_fee_event() {
+    let mut accounts = RouteAccounts::new();
+    accounts.platform_fee = Pubkey::new_unique();
+    let fee_event = fee_event_data(accounts.platform_fee, USDC, 120_000);
+
+    let transaction = route_transaction(&accounts, event_cpis(&[fee_event])).build();
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    let commission = decoded.swaps[0].swap.commission.clone().unwrap();
+    assert_eq!(commission.amount, Some(120_000));
+}
+
+/// Without inner instructions the fee account's balance change is all that
+/// is left to go by.
+#[test]
+fn platform_fee_without_inner_instructions() {
+    let mut accounts = RouteAccounts::new();
+    accounts.platform_fee = Pubkey::new_unique();
+
+    let transaction = route_transaction(&accounts, vec![])
+        .without_inner_instructions()
+        .build();
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    let commission = decoded.swaps[0].swap.commission.clone().unwrap();
+    assert_eq!(commission.amount, Some(300_000));
+}
+
+#[test]
+fn route_without_platform_fee() {
+    let decoded =
+        decode_transaction(&route_transaction(&RouteAccounts::new(), vec![]).build()).unwrap();
+
+    assert_eq!(decoded.swaps[0].swap.commission, None);
+}
+
+/// Exact-out routes charge the platform fee in the input token.
+#[test]
+fn exact_out_route_platform_fee() {
+    let mut accounts = RouteAccounts::new();
+    accounts.platform_fee = Pubkey::new_unique();
+    let mut data = EXACT_OUT_ROUTE.to_vec();
+    route_plan(&mut data);
+    data.extend(150_000_000u64.to_le_bytes());
+    data.extend(1_000_000_000u64.to_le_bytes());
+    data.extend(100u16.to_le_bytes());
+    data.push(85);
+    let keys = vec![
+        TOKEN_PROGRAM,
+        accounts.user,
+        accounts.user_source,
+        accounts.user_destination,
+        accounts.destination,
+        WSOL,
+        USDC,
+        accounts.platform_fee,
+        TOKEN_2022_PROGRAM,
+        Pubkey::new_unique(),
+        JUPITER,
+    ];
+
+    let swap = decode_instruction(data, keys, JUPITER).unwrap();
+
+    let commission = swap.commission.unwrap();
+    assert_eq!(commission.recipient, accounts.platform_fee);
+    assert_eq!(commission.mint, WSOL);
+    assert_eq!(commission.bps, 85);
+    assert_eq!(commission.side, FeeSide::Input);
+    assert_eq!(commission.amount, None);
+}
+
+const SET_TOKEN_LEDGER: [u8; 8] = [0xe4, 0x55, 0xb9, 0x70, 0x4e, 0x4f, 0x4d, 0x02];
+
+// Account indexes of the token-ledger fixture transaction.
+const LEDGER_USER_SOURCE: u8 = 1;
+const LEDGER_USER_DESTINATION: u8 = 2;
+const LEDGER_POOL_VAULT: u8 = 8;
+const LEDGER_FUNDING: u8 = 9;
+
+/// Spent by the ledger route, in two transfers of 600_000_000 and
+/// 400_000_000 out of the user's WSOL account.
+const LEDGER_ROUTED: u64 = 1_000_000_000;
+/// Moved into the user's WSOL account before the route, so that its net
+/// decrease is smaller than what the route spent.
+const LEDGER_FUNDED: u64 = 250_000_000;
+
+/// A transaction of WSOL to USDC `RouteWithTokenLedger` instructions and
+/// whatever else `outer` holds. `route_inner` are the CPIs of outer
+/// instruction `route_index`; without them the meta records no inner
+/// instructions at all.
+fn ledger_transaction(
+    outer: Vec<Value>,
+    route_index: usize,
+    route_inner: Option<Vec<Value>>,
+) -> EncodedConfirmedTransactionWithStatusMeta {
+    let user = Pubkey::new_unique();
+    let mut transaction = TransactionBuilder::new(vec![
+        user,
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        TOKEN_PROGRAM,
+        JUPITER,
+        USDC,
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ])
+    .token_balance(
+        LEDGER_USER_SOURCE,
+        WSOL,
+        user,
+        Some(1_750_000_000),
+        Some(1_000_000_000),
+    )
+    .token_balance(
+        LEDGER_USER_DESTINATION,
+        USDC,
+        user,
+        Some(0),
+        Some(150_100_000),
+    )
+    .token_balance(LEDGER_FUNDING, WSOL, user, Some(LEDGER_FUNDED), Some(0));
+    for instruction in outer {
+        transaction = transaction.instruction(instruction);
+    }
+    match route_inner {
+        Some(instructions) => transaction.inner(route_index, instructions),
+        None => transaction.without_inner_instructions(),
+    }
+    .build()
+}
+
+fn set_token_ledger(token_account: u8) -> Value {
+    instruction(4, &[6, token_account], &SET_TOKEN_LEDGER, None)
+}
+
+/// A user transfer, signed by the wallet at index 0.
+fn token_transfer(source: u8, destination: u8, amount: u64, stack_height: u32) -> Value {
+    common::token_transfer(3, source, destination, 0, amount, stack_height)
+}
+
+fn ledger_route() -> Value {
+    let mut data = ROUTE_WITH_TOKEN_LEDGER.to_vec();
+    route_plan(&mut data);
+    data.extend(150_000_000u64.to_le_bytes());
+    data.extend(50u16.to_le_bytes());
+    data.push(0);
+    instruction(
+        4,
+        &[
+            3,
+            0,
+            LEDGER_USER_SOURCE,
+            LEDGER_USER_DESTINATION,
+            4,
+            5,
+            4,
+            6,
+            7,
+            4,
+        ],
+        &data,
+        None,
+    )
+}
+
+/// The CPIs of the ledger route: both legs pay out of the user's WSOL
+/// account and the pool pays the user back in USDC.
+fn routed_transfers() -> Vec<Value> {
+    vec![
+        token_transfer(LEDGER_USER_SOURCE, LEDGER_POOL_VAULT, 600_000_000, 2),
+        token_transfer(LEDGER_USER_SOURCE, LEDGER_POOL_VAULT, 400_000_000, 2),
+        token_transfer(LEDGER_POOL_VAULT, LEDGER_USER_DESTINATION, 150_100_000, 2),
+    ]
+}
+
+/// The route swaps what it moved out of the snapshotted account, not the
+/// account's net decrease, which the funding transfer offsets.
+#[test]
+fn ledger_route_input_from_routed_transfers() {
+    let transaction = ledger_transaction(
+        vec![
+            set_token_ledger(LEDGER_USER_SOURCE),
+            token_transfer(LEDGER_FUNDING, LEDGER_USER_SOURCE, LEDGER_FUNDED, 1),
+            ledger_route(),
+        ],
+        2,
+        Some(routed_transfers()),
+    );
+
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    assert_eq!(decoded.swaps.len(), 1);
+    let swap = &decoded.swaps[0].swap;
+    assert_eq!(swap.mint_token_in, Some(WSOL));
+    assert_eq!(swap.amount_in, Some(LEDGER_ROUTED));
+    assert_eq!(swap.realized_in, Some(LEDGER_ROUTED));
+    assert_eq!(swap.realized_out, Some(150_100_000));
+}
+
+/// Without recorded CPIs the route swaps what was moved into the
+/// snapshotted account after the snapshot, whatever the account's net change
+/// over the transaction.
+#[test]
+fn ledger_route_input_from_funding_without_inner_instructions() {
+    let transaction = ledger_transaction(
+        vec![
+            set_token_ledger(LEDGER_USER_SOURCE),
+            token_transfer(LEDGER_FUNDING, LEDGER_USER_SOURCE, LEDGER_FUNDED, 1),
+            ledger_route(),
+        ],
+        2,
+        None,
+    );
+
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    let swap = &decoded.swaps[0].swap;
+    assert_eq!(swap.amount_in, Some(LEDGER_FUNDED));
+}
+
+/// Funding that precedes the snapshot is not swapped, and nothing else tells
+/// the route's input.
+#[test]
+fn ledger_route_funded_before_the_snapshot_without_inner_instructions() {
+    let transaction = ledger_transaction(
+        vec![
+            token_transfer(LEDGER_FUNDING, LEDGER_USER_SOURCE, LEDGER_FUNDED, 1),
+            set_token_ledger(LEDGER_USER_SOURCE),
+            ledger_route(),
+        ],
+        2,
+        None,
+    );
+
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    let swap = &decoded.swaps[0].swap;
+    assert_eq!(swap.amount_in, None);
+}
+
+/// Snapshots taken after the route do not count; the route's own source
+/// account is used instead.
+#[test]
+fn ledger_route_without_a_preceding_snapshot() {
+    let transaction = ledger_transaction(
+        vec![
+            token_transfer(LEDGER_FUNDING, LEDGER_USER_SOURCE, LEDGER_FUNDED, 1),
+            ledger_route(),
+            set_token_ledger(LEDGER_FUNDING),
+        ],
+        1,
+        Some(routed_transfers()),
+    );
+
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    let swap = &decoded.swaps[0].swap;
+    assert_eq!(swap.amount_in, Some(LEDGER_ROUTED));
+    assert_eq!(swap.realized_in, Some(LEDGER_ROUTED));
+}
@@ -0,0 +1,711 @@
+mod common;
+
+use carbon_okx_dex_decoder::builder::BuildInstruction;
+use carbon_okx_dex_decoder::dex_programs::RouteMismatch;
+use carbon_okx_dex_decoder::instructions::commission_sol_from_swap::{
+    CommissionSolFromSwap, CommissionSolFromSwapInstructionAccounts,
+};
+use carbon_okx_dex_decoder::instructions::commission_sol_swap2::{
+    CommissionSolSwap2, CommissionSolSwap2InstructionAccounts,
+};
+use carbon_okx_dex_decoder::instructions::commission_spl_from_swap::{
+    CommissionSplFromSwap, CommissionSplFromSwapInstructionAccounts,
+};
+use carbon_okx_dex_decoder::instructions::commission_spl_swap2::{
+    CommissionSplSwap2, CommissionSplSwap2InstructionAccounts,
+};
+use carbon_okx_dex_decoder::instructions::swap::{Swap, SwapInstructionAccounts};
+use carbon_okx_dex_decoder::programs::{OkxProgram, OkxPrograms};
+use carbon_okx_dex_decoder::types::{
+    AdaptorID, BridgeToArgs, CommissionSwapArgs, Dex, Route, SwapArgs, SwapType,
+};
+use carbon_okx_dex_decoder::validation::RouteViolation;
+use carbon_okx_dex_decoder::PROGRAM_ID as OKX;
+use common::{instruction, TransactionBuilder};
+use serde_json::Value;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use teste::{
+    decode_instruction_with_metas, decode_transaction, decode_transaction_with_context,
+    BridgeTransfer, DecodeContext, FeeSide,
+};
+
+const WHIRLPOOL: Pubkey = pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+const METEORA_DLMM: Pubkey = pubkey!("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo");
+/// A program no DEX of the fixture route runs on.
+const UNKNOWN_PROGRAM: Pubkey = Pubkey::new_from_array([7; 32]);
+const WSOL: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+const SWAP_EVENT: [u8; 16] = [
+    0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d, 0x40, 0xc6, 0xcd, 0xe8, 0x26, 0x08, 0x71, 0xe2,
+];
+
+// Account indexes of the fixture transaction.
+const OKX_INDEX: u8 = 5;
+const WHIRLPOOL_INDEX: u8 = 6;
+const METEORA_DLMM_INDEX: u8 = 7;
+const UNKNOWN_PROGRAM_INDEX: u8 = 8;
+const EVENT_AUTHORITY_INDEX: u8 = 9;
+
+/// An OKX `Swap` of 1_000 split 600/400 between a Whirlpool and a Meteora
+/// DLMM pool, with the CPIs the router made given as inner instructions.
+fn swap_transaction(inner_instructions: Vec<Value>) -> EncodedConfirmedTransactionWithStatusMeta {
+    router_swap_transaction(OKX, inner_instructions)
+}
+
+/// Like [`swap_transaction`], sent to the router deployed at `router`.
+fn router_swap_transaction(
+    router: Pubkey,
+    inner_instructions: Vec<Value>,
+) -> EncodedConfirmedTransactionWithStatusMeta {
+    let swap = Swap {
+        data: SwapArgs {
+            amount_in: 1_000,
+            expect_amount_out: 990,
+            min_return: 980,
+            amounts: vec![600, 400],
+            routes: vec![
+                vec![Route {
+                    dexes: vec![Dex::Whirlpool],
+                    weights: vec![100],
+                }],
+                vec![Route {
+                    dexes: vec![Dex::MeteoraDlmm],
+                    weights: vec![100],
+                }],
+            ],
+        },
+    };
+    let keys = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        router,
+        WHIRLPOOL,
+        METEORA_DLMM,
+        UNKNOWN_PROGRAM,
+        Pubkey::new_unique(),
+    ];
+    let swap_instruction = swap.instruction(
+        &SwapInstructionAccounts {
+            payer: keys[0],
+            source_token_account: keys[1],
+            destination_token_account: keys[2],
+            source_mint: keys[3],
+            destination_mint: keys[4],
+        },
+        &[],
+    );
+
+    TransactionBuilder::new(keys.to_vec())
+        .readonly_unsigned(7)
+        .instruction(instruction(
+            OKX_INDEX,
+            &[0, 1, 2, 3, 4],
+            &swap_instruction.data,
+            None,
+        ))
+        .inner(0, inner_instructions)
+        .build()
+}
+
+/// A CPI from the router into a DEX program.
+fn dex_call(program_index: u8) -> Value {
+    instruction(program_index, &[1, 2], &[], Some(2))
+}
+
+/// The swap event the router emits after a leg, as a CPI into itself.
+fn swap_event(dex: u8, amount_in: u64, amount_out: u64) -> Value {
+    let mut data = SWAP_EVENT.to_vec();
+    data.push(dex);
+    data.extend(amount_in.to_le_bytes());
+    data.extend(amount_out.to_le_bytes());
+    instruction(OKX_INDEX, &[EVENT_AUTHORITY_INDEX], &data, Some(2))
+}
+
+const WHIRLPOOL_TAG: u8 = 2;
+const METEORA_DLMM_TAG: u8 = 13;
+
+#[test]
+fn events_fill_every_leg_and_the_realized_totals() {
+    let transaction = swap_transaction(vec![
+        dex_call(WHIRLPOOL_INDEX),
+        swap_event(WHIRLPOOL_TAG, 600, 590),
+        dex_call(METEORA_DLMM_INDEX),
+        swap_event(METEORA_DLMM_TAG, 400, 395),
+    ]);
+
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    // The events are folded into the route rather than reported as swaps.
+    assert_eq!(decoded.swaps.len(), 1);
+    let swap = &decoded.swaps[0].swap;
+    let executed: Vec<_> = swap
+        .okx_legs
+        .iter()
+        .map(|leg| (leg.dex.clone(), leg.amount_in, leg.amount_out))
+        .collect();
+    assert_eq!(
+        executed,
+        vec![
+            (Dex::Whirlpool, Some(600), Some(590)),
+            (Dex::MeteoraDlmm, Some(400), Some(395)),
+        ]
+    );
+    assert_eq!(swap.realized_in, Some(1_000));
+    assert_eq!(swap.realized_out, Some(985));
+    assert!(swap.route_mismatches.is_empty());
+}
+
+/// Without an event for every leg the route's totals stay unknown, while
+/// the legs that were matched keep their amounts.
+#[test]
+fn partially_matched_route_has_no_totals() {
+    let transaction = swap_transaction(vec![
+        dex_call(WHIRLPOOL_INDEX),
+        swap_event(WHIRLPOOL_TAG, 600, 590),
+        dex_call(METEORA_DLMM_INDEX),
+    ]);
+
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    assert_eq!(decoded.swaps.len(), 1);
+    let swap = &decoded.swaps[0].swap;
+    assert_eq!(swap.okx_legs[0].amount_out, Some(590));
+    assert_eq!(swap.okx_legs[1].amount_in, Some(400));
+    assert_eq!(swap.okx_legs[1].amount_out, None);
+    assert_eq!(swap.realized_in, None);
+    assert_eq!(swap.realized_out, None);
+}
+
+/// An event for a DEX the route does not name matches no leg.
+#[test]
+fn event_for_an_undeclared_dex_is_ignored() {
+    let transaction = swap_transaction(vec![
+        dex_call(WHIRLPOOL_INDEX),
+        swap_event(WHIRLPOOL_TAG, 600, 590),
+        dex_call(UNKNOWN_PROGRAM_INDEX),
+        swap_event(4, 400, 395),
+    ]);
+
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    let swap = &decoded.swaps[0].swap;
+    assert_eq!(swap.okx_legs[1].amount_out, None);
+    assert_eq!(swap.realized_out, None);
+}
+
+/// A route that names Meteora DLMM but hands the second leg to another
+/// program is flagged on both counts.
+#[test]
+fn spoofed_route_is_flagged() {
+    let transaction = swap_transaction(vec![
+        dex_call(WHIRLPOOL_INDEX),
+        swap_event(WHIRLPOOL_TAG, 600, 590),
+        dex_call(UNKNOWN_PROGRAM_INDEX),
+        swap_event(METEORA_DLMM_TAG, 400, 395),
+    ]);
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    assert_eq!(
+        decoded.swaps[0].swap.route_mismatches,
+        vec![
+            RouteMismatch::DexNotInvoked {
+                dex: Dex::MeteoraDlmm
+            },
+            RouteMismatch::UndeclaredProgram {
+                program_id: UNKNOWN_PROGRAM
+            },
+        ]
+    );
+}
+
+/// Events are emitted by the router itself, so a route whose DEX was never
+/// called is flagged even when an event claims the leg was executed.
+#[test]
+fn event_without_a_dex_call_is_flagged() {
+    let transaction = swap_transaction(vec![
+        dex_call(WHIRLPOOL_INDEX),
+        swap_event(WHIRLPOOL_TAG, 600, 590),
+        swap_event(METEORA_DLMM_TAG, 400, 395),
+    ]);
+
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    assert_eq!(
+        decoded.swaps[0].swap.route_mismatches,
+        vec![RouteMismatch::DexNotInvoked {
+            dex: Dex::MeteoraDlmm
+        }]
+    );
+}
+
+fn matched_route() -> Vec<Value> {
+    vec![
+        dex_call(WHIRLPOOL_INDEX),
+        swap_event(WHIRLPOOL_TAG, 600, 590),
+        dex_call(METEORA_DLMM_INDEX),
+        swap_event(METEORA_DLMM_TAG, 400, 395),
+    ]
+}
+
+/// Swaps on a router outside the configured set are not OKX instructions,
+/// so they are skipped rather than reported as failures.
+#[test]
+fn replaced_router_set_skips_the_mainnet_router() {
+    let transaction = swap_transaction(matched_route());
+    let devnet_only = OkxPrograms::new(vec![OkxProgram {
+        program_id: Pubkey::new_unique(),
+        version: None,
+    }]);
+
+    let decoded = decode_transaction_with_context(
+        &transaction,
+        DecodeContext {
+            okx_programs: Some(&devnet_only),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert!(decoded.swaps.is_empty());
+    assert!(decoded.failures.is_empty());
+}
+
+#[test]
+fn registered_router_is_decoded_with_its_events() {
+    let proxy = Pubkey::new_unique();
+    let transaction = router_swap_transaction(proxy, matched_route());
+    let mut programs = OkxPrograms::default();
+    programs.register(proxy, Some("v3"));
+
+    let decoded = decode_transaction_with_context(
+        &transaction,
+        DecodeContext {
+            okx_programs: Some(&programs),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(decoded.swaps.len(), 1);
+    assert_eq!(decoded.swaps[0].program_id, proxy);
+    assert_eq!(decoded.swaps[0].swap.realized_out, Some(985));
+    assert!(decoded.swaps[0].swap.route_mismatches.is_empty());
+
+    let unregistered = decode_transaction(&transaction).unwrap();
+    assert!(unregistered.swaps.is_empty());
+    assert!(unregistered.failures.is_empty());
+}
+
+/// Commission of the commission fixtures, in basis points.
+const COMMISSION_BPS: u16 = 100;
+
+/// The user's accounts of the commission fixtures, plus the account the
+/// commission is paid to.
+struct CommissionAccounts {
+    payer: Pubkey,
+    source_token_account: Pubkey,
+    destination_token_account: Pubkey,
+    source_mint: Pubkey,
+    destination_mint: Pubkey,
+    commission: Pubkey,
+}
+
+impl CommissionAccounts {
+    fn new() -> Self {
+        CommissionAccounts {
+            payer: Pubkey::new_unique(),
+            source_token_account: Pubkey::new_unique(),
+            destination_token_account: Pubkey::new_unique(),
+            source_mint: Pubkey::new_unique(),
+            destination_mint: Pubkey::new_unique(),
+            commission: Pubkey::new_unique(),
+        }
+    }
+}
+
+/// 1_000_000 swapped 600_000/400_000 through the Whirlpool and Meteora DLMM
+/// legs of [`swap_transaction`].
+fn commission_swap_args() -> SwapArgs {
+    SwapArgs {
+        amount_in: 1_000_000,
+        expect_amount_out: 990_000,
+        min_return: 980_000,
+        amounts: vec![600_000, 400_000],
+        routes: vec![
+            vec![Route {
+                dexes: vec![Dex::Whirlpool],
+                weights: vec![100],
+            }],
+            vec![Route {
+                dexes: vec![Dex::MeteoraDlmm],
+                weights: vec![100],
+            }],
+        ],
+    }
+}
+
+fn commission_args(commission_direction: bool) -> CommissionSwapArgs {
+    let args = commission_swap_args();
+    CommissionSwapArgs {
+        amount_in: args.amount_in,
+        expect_amount_out: args.expect_amount_out,
+        min_return: args.min_return,
+        amounts: args.amounts,
+        routes: args.routes,
+        commission_rate: COMMISSION_BPS,
+        commission_direction,
+    }
+}
+
+fn bridge_to_args() -> BridgeToArgs {
+    BridgeToArgs {
+        adaptor_id: AdaptorID::Wormhole,
+        to: vec![0xab; 20],
+        order_id: 7,
+        to_chain_id: 8453,
+        amount: 980_000,
+        swap_type: SwapType::SWAPANDBRIDGE,
+        data: vec![],
+        ext_data: vec![],
+    }
+}
+
+fn spl_commission_swap(accounts: &CommissionAccounts, from_input: bool) -> Instruction {
+    CommissionSplSwap2 {
+        data: commission_args(from_input),
+        order_id: 1,
+    }
+    .instruction(
+        &CommissionSplSwap2InstructionAccounts {
+            payer: accounts.payer,
+            source_token_account: accounts.source_token_account,
+            destination_token_account: accounts.destination_token_account,
+            source_mint: accounts.source_mint,
+            destination_mint: accounts.destination_mint,
+            commission_token_account: accounts.commission,
+            token_program: common::TOKEN_PROGRAM,
+        },
+        &[],
+    )
+}
+
+fn sol_commission_swap(accounts: &CommissionAccounts, from_input: bool) -> Instruction {
+    CommissionSolSwap2 {
+        data: commission_args(from_input),
+        order_id: 1,
+    }
+    .instruction(
+        &CommissionSolSwap2InstructionAccounts {
+            payer: accounts.payer,
+            source_token_account: accounts.source_token_account,
+            destination_token_account: accounts.destination_token_account,
+            source_mint: accounts.source_mint,
+            destination_mint: accounts.destination_mint,
+            commission_account: accounts.commission,
+            system_program: pubkey!("11111111111111111111111111111111"),
+        },
+        &[],
+    )
+}
+
+fn decode_commission(built: Instruction) -> teste::Commission {
+    decode_instruction_with_metas(built.data, built.accounts, built.program_id)
+        .unwrap()
+        .commission
+        .unwrap()
+}
+
+/// An input-side commission is carved out of what the user pays, so the
+/// amount swapped is net of it: 1_000_000 * 100 / 9_900.
+#[test]
+fn spl_commission_from_the_input() {
+    let accounts = CommissionAccounts::new();
+
+    let commission = decode_commission(spl_commission_swap(&accounts, true));
+
+    assert_eq!(commission.recipient, accounts.commission);
+    assert_eq!(commission.mint, accounts.source_mint);
+    assert_eq!(commission.bps, COMMISSION_BPS);
+    assert_eq!(commission.side, FeeSide::Input);
+    assert_eq!(commission.amount, Some(10_101));
+}
+
+/// An output-side commission depends on the executed output, which a lone
+/// instruction does not know.
+#[test]
+fn spl_commission_from_the_output() {
+    let accounts = CommissionAccounts::new();
+
+    let commission = decode_commission(spl_commission_swap(&accounts, false));
+
+    assert_eq!(commission.recipient, accounts.commission);
+    assert_eq!(commission.mint, accounts.destination_mint);
+    assert_eq!(commission.side, FeeSide::Output);
+    assert_eq!(commission.amount, None);
+}
+
+/// SOL commissions are paid out in lamports and reported in wrapped SOL,
+/// whichever side they are taken from.
+#[test]
+fn sol_commission_from_either_side() {
+    let accounts = CommissionAccounts::new();
+
+    let commission = decode_commission(sol_commission_swap(&accounts, true));
+    assert_eq!(commission.recipient, accounts.commission);
+    assert_eq!(commission.mint, WSOL);
+    assert_eq!(commission.side, FeeSide::Input);
+    assert_eq!(commission.amount, Some(10_101));
+
+    let commission = decode_commission(sol_commission_swap(&accounts, false));
+    assert_eq!(commission.recipient, accounts.commission);
+    assert_eq!(commission.mint, WSOL);
+    assert_eq!(commission.side, FeeSide::Output);
+    assert_eq!(commission.amount, None);
+}
+
+/// `*_from_swap` instructions carry no direction and always charge the
+/// input.
+#[test]
+fn from_swap_commissions_are_charged_on_the_input() {
+    let accounts = CommissionAccounts::new();
+    let spl = CommissionSplFromSwap {
+        args: commission_swap_args(),
+        commission_rate: COMMISSION_BPS,
+        bridge_to_args: bridge_to_args(),
+        offset: 0,
+        len: 0,
+    }
+    .instruction(
+        &CommissionSplFromSwapInstructionAccounts {
+            payer: accounts.payer,
+            source_token_account: accounts.source_token_account,
+            destination_token_account: accounts.destination_token_account,
+            source_mint: accounts.source_mint,
+            destination_mint: accounts.destination_mint,
+            bridge_program: Pubkey::new_unique(),
+            associated_token_program: Pubkey::new_unique(),
+            token_program: common::TOKEN_PROGRAM,
+            token_2022_program: Pubkey::new_unique(),
+            system_program: Pubkey::new_unique(),
+            commission_token_account: accounts.commission,
+        },
+        &[],
+    );
+    let sol = CommissionSolFromSwap {
+        args: commission_swap_args(),
+        commission_rate: COMMISSION_BPS,
+        bridge_to_args: bridge_to_args(),
+        offset: 0,
+        len: 0,
+    }
+    .instruction(
+        &CommissionSolFromSwapInstructionAccounts {
+            payer: accounts.payer,
+            source_token_account: accounts.source_token_account,
+            destination_token_account: accounts.destination_token_account,
+            source_mint: accounts.source_mint,
+            destination_mint: accounts.destination_mint,
+            bridge_program: Pubkey::new_unique(),
+            associated_token_program: Pubkey::new_unique(),
+            token_program: common::TOKEN_PROGRAM,
+            token_2022_program: Pubkey::new_unique(),
+            system_program: Pubkey::new_unique(),
+            commission_account: accounts.commission,
+        },
+        &[],
+    );
+
+    let commission = decode_commission(spl);
+    assert_eq!(commission.recipient, accounts.commission);
+    assert_eq!(commission.mint, accounts.source_mint);
+    assert_eq!(commission.side, FeeSide::Input);
+    assert_eq!(commission.amount, Some(10_101));
+
+    let commission = decode_commission(sol);
+    assert_eq!(commission.recipient, accounts.commission);
+    assert_eq!(commission.mint, WSOL);
+    assert_eq!(commission.side, FeeSide::Input);
+    assert_eq!(commission.amount, Some(10_101));
+}
+
+/// `built` as the router instruction of a transaction laid out like
+/// [`swap_transaction`], with the commission account and token program
+/// appended to the fixture's accounts.
+fn commission_transaction(
+    built: &Instruction,
+    inner_instructions: Vec<Value>,
+) -> TransactionBuilder {
+    let mut keys: Vec<Pubkey> = built.accounts[..5]
+        .iter()
+        .map(|account| account.pubkey)
+        .collect();
+    keys.extend([
+        OKX,
+        WHIRLPOOL,
+        METEORA_DLMM,
+        UNKNOWN_PROGRAM,
+        Pubkey::new_unique(),
+    ]);
+    let mut indexes: Vec<u8> = (0..5).collect();
+    for account in &built.accounts[5..] {
+        indexes.push(keys.len() as u8);
+        keys.push(account.pubkey);
+    }
+
+    TransactionBuilder::new(keys)
+        .instruction(instruction(OKX_INDEX, &indexes, &built.data, None))
+        .inner(0, inner_instructions)
+}
+
+/// Swap events report the route's gross output, which the commission is
+/// taken out of: 990_000 * 100 / 10_000.
+#[test]
+fn output_commission_on_the_executed_output() {
+    let accounts = CommissionAccounts::new();
+    let transaction = commission_transaction(
+        &spl_commission_swap(&accounts, false),
+        vec![
+            dex_call(WHIRLPOOL_INDEX),
+            swap_event(WHIRLPOOL_TAG, 600_000, 594_000),
+            dex_call(METEORA_DLMM_INDEX),
+            swap_event(METEORA_DLMM_TAG, 400_000, 396_000),
+        ],
+    )
+    .build();
+
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    let commission = decoded.swaps[0].swap.commission.clone().unwrap();
+    assert_eq!(commission.side, FeeSide::Output);
+    assert_eq!(commission.amount, Some(9_900));
+}
+
+/// Without events only what reached the user is known, which is net of the
+/// commission: 980_100 * 100 / 9_900.
+#[test]
+fn output_commission_on_the_received_output() {
+    let accounts = CommissionAccounts::new();
+    let transaction = commission_transaction(&spl_commission_swap(&accounts, false), vec![])
+        .without_inner_instructions()
+        .token_balance(
+            2,
+            accounts.destination_mint,
+            accounts.payer,
+            Some(0),
+            Some(980_100),
+        )
+        .build();
+
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    let swap = &decoded.swaps[0].swap;
+    assert_eq!(swap.realized_out, Some(980_100));
+    assert_eq!(swap.commission.clone().unwrap().amount, Some(9_900));
+}
+
+fn bridge_transfer(to: Vec<u8>, to_chain_id: u64) -> BridgeTransfer {
+    BridgeTransfer::from(&BridgeToArgs {
+        to,
+        to_chain_id,
+        ..bridge_to_args()
+    })
+}
+
+#[test]
+fn bridge_names_the_adaptor_and_chain() {
+    let bridge = bridge_transfer(vec![0xab; 20], 8453);
+
+    assert_eq!(bridge.bridge.as_deref(), Some("Wormhole"));
+    assert_eq!(bridge.to_chain.as_deref(), Some("Base"));
+    assert_eq!(bridge.to, format!("0x{}", "ab".repeat(20)));
+}
+
+/// EVM recipients may also come as a 32-byte word, left-padded with zeros.
+#[test]
+fn padded_evm_recipient() {
+    let mut to = vec![0; 12];
+    to.extend([0xcd; 20]);
+
+    let bridge = bridge_transfer(to, 1);
+
+    assert_eq!(bridge.to_chain.as_deref(), Some("Ethereum"));
+    assert_eq!(bridge.to, format!("0x{}", "cd".repeat(20)));
+}
+
+/// The USDT contract on Tron, `41a614f8…d13c` in hex.
+#[test]
+fn tron_recipient_in_base58check() {
+    let to = [
+        0xa6, 0x14, 0xf8, 0x03, 0xb6, 0xfd, 0x78, 0x09, 0x86, 0xa4, 0x2c, 0x78, 0xec, 0x9c, 0x7f,
+        0x77, 0xe6, 0xde, 0xd1, 0x3c,
+    ];
+
+    let bridge = bridge_transfer(to.to_vec(), 195);
+
+    assert_eq!(bridge.to_chain.as_deref(), Some("Tron"));
+    assert_eq!(bridge.to, "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t");
+}
+
+#[test]
+fn solana_recipient_in_base58() {
+    let recipient = Pubkey::new_unique();
+
+    let bridge = bridge_transfer(recipient.to_bytes().to_vec(), 501);
+
+    assert_eq!(bridge.to, recipient.to_string());
+}
+
+/// Recipients passed as the address string are kept as they are, including
+/// the `-` and `_` of base64url TON addresses.
+#[test]
+fn recipient_passed_as_a_string() {
+    let ton = "EQD-cvR0Nz6XAyRBvbhz_abTrRC6sI5tvHvvpeQraV9UAAD7";
+
+    let bridge = bridge_transfer(ton.as_bytes().to_vec(), 607);
+
+    assert_eq!(bridge.to_chain.as_deref(), Some("TON"));
+    assert_eq!(bridge.to, ton);
+}
+
+#[test]
+fn unknown_chain_recipient_in_hex() {
+    let bridge = bridge_transfer(vec![0x01, 0x02, 0xff], 999_999);
+
+    assert_eq!(bridge.to_chain, None);
+    assert_eq!(bridge.to, "0102ff");
+}
+
+/// A route whose split amounts fall short of its input is still decoded,
+/// with the violation attached.
+#[test]
+fn route_violations_reach_the_swap() {
+    let mut args = commission_swap_args();
+    args.amounts = vec![600_000, 300_000];
+    let built = Swap { data: args }.instruction(
+        &SwapInstructionAccounts {
+            payer: Pubkey::new_unique(),
+            source_token_account: Pubkey::new_unique(),
+            destination_token_account: Pubkey::new_unique(),
+            source_mint: Pubkey::new_unique(),
+            destination_mint: Pubkey::new_unique(),
+        },
+        &[],
+    );
+
+    let swap = decode_instruction_with_metas(built.data, built.accounts, OKX).unwrap();
+
+    assert_eq!(swap.okx_legs.len(), 2);
+    assert_eq!(
+        swap.route_violations,
+        vec![RouteViolation::AmountsSumMismatch {
+            sum: 900_000,
+            amount_in: 1_000_000
+        }]
+    );
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use carbon_pump_swap_decoder::PROGRAM_ID as PUMP_SWAP;
+use common::{instruction_data, unique_keys};
+use solana_sdk::pubkey::Pubkey;
+use teste::{decode_instruction, LiquidityAction, SwapMode};
+
+const BUY: [u8; 8] = [0x66, 0x06, 0x3d, 0x12, 0x01, 0xda, 0xeb, 0xea];
+const DEPOSIT: [u8; 8] = [0xf2, 0x23, 0xc6, 0x89, 0x52, 0xe1, 0xf2, 0xb6];
+const WITHDRAW: [u8; 8] = [0xb7, 0x12, 0x46, 0x9c, 0x94, 0x6d, 0xa1, 0x22];
+const CREATE_POOL: [u8; 8] = [0xe9, 0x92, 0xd1, 0x8e, 0xcf, 0x68, 0x40, 0xbc];
+
+// Account positions of `Buy`.
+const BUY_USER: usize = 1;
+const BUY_BASE_MINT: usize = 3;
+const BUY_QUOTE_MINT: usize = 4;
+const BUY_USER_BASE_TOKEN: usize = 5;
+const BUY_USER_QUOTE_TOKEN: usize = 6;
+
+// Account positions shared by `Deposit`, `Withdraw` and `CreatePool`.
+const POOL: usize = 0;
+const USER: usize = 2;
+const BASE_MINT: usize = 3;
+const QUOTE_MINT: usize = 4;
+const LP_MINT: usize = 5;
+
+/// A buy pays quote for an exact amount of base.
+#[test]
+fn buy_trades_quote_for_base() {
+    let keys = unique_keys(19);
+
+    let swap = decode_instruction(
+        instruction_data(&BUY, &[5_000, 2_000_000]),
+        keys.clone(),
+        PUMP_SWAP,
+    )
+    .unwrap();
+
+    assert_eq!(swap.swap_mode, Some(SwapMode::ExactOut));
+    assert_eq!(swap.amount_out, Some(5_000));
+    assert_eq!(swap.max_amount_in, Some(2_000_000));
+    assert_eq!(swap.mint_token_in, Some(keys[BUY_QUOTE_MINT]));
+    assert_eq!(swap.mint_token_out, Some(keys[BUY_BASE_MINT]));
+    assert_eq!(swap.mint_token_account_in, Some(keys[BUY_USER_QUOTE_TOKEN]));
+    assert_eq!(swap.mint_token_account_out, Some(keys[BUY_USER_BASE_TOKEN]));
+    assert_eq!(swap.user, Some(keys[BUY_USER]));
+}
+
+#[test]
+fn deposit_reports_its_maximum_amounts() {
+    let keys = unique_keys(15);
+
+    let swap = decode_instruction(
+        instruction_data(&DEPOSIT, &[700, 5_000, 2_000_000]),
+        keys.clone(),
+        PUMP_SWAP,
+    )
+    .unwrap();
+
+    assert_eq!(swap.mint_token_in, None);
+    assert_eq!(swap.mint_token_out, None);
+    assert_eq!(swap.user, Some(keys[USER]));
+    let liquidity = swap.liquidity.unwrap();
+    assert_eq!(liquidity.action, LiquidityAction::Deposit);
+    assert_eq!(liquidity.pool, keys[POOL]);
+    assert_eq!(liquidity.base_mint, keys[BASE_MINT]);
+    assert_eq!(liquidity.quote_mint, keys[QUOTE_MINT]);
+    assert_eq!(liquidity.lp_mint, keys[LP_MINT]);
+    assert_eq!(liquidity.lp_amount, Some(700));
+    assert_eq!(liquidity.base_amount, 5_000);
+    assert_eq!(liquidity.quote_amount, 2_000_000);
+}
+
+#[test]
+fn withdraw_reports_its_minimum_amounts() {
+    let keys = unique_keys(15);
+
+    let swap = decode_instruction(
+        instruction_data(&WITHDRAW, &[700, 4_900, 1_950_000]),
+        keys.clone(),
+        PUMP_SWAP,
+    )
+    .unwrap();
+
+    assert_eq!(swap.mint_token_in, None);
+    assert_eq!(swap.mint_token_out, None);
+    let liquidity = swap.liquidity.unwrap();
+    assert_eq!(liquidity.action, LiquidityAction::Withdraw);
+    assert_eq!(liquidity.pool, keys[POOL]);
+    assert_eq!(liquidity.base_mint, keys[BASE_MINT]);
+    assert_eq!(liquidity.quote_mint, keys[QUOTE_MINT]);
+    assert_eq!(liquidity.lp_mint, keys[LP_MINT]);
+    assert_eq!(liquidity.lp_amount, Some(700));
+    assert_eq!(liquidity.base_amount, 4_900);
+    assert_eq!(liquidity.quote_amount, 1_950_000);
+}
+
+#[test]
+fn create_pool_reports_the_seed_liquidity() {
+    let keys = unique_keys(18);
+    let mut create_pool = CREATE_POOL.to_vec();
+    create_pool.extend(0u16.to_le_bytes());
+    create_pool.extend(200_000_000u64.to_le_bytes());
+    create_pool.extend(80_000_000_000u64.to_le_bytes());
+    create_pool.extend(Pubkey::new_unique().to_bytes());
+
+    let swap = decode_instruction(create_pool, keys.clone(), PUMP_SWAP).unwrap();
+
+    assert_eq!(swap.mint_token_in, None);
+    assert_eq!(swap.mint_token_out, None);
+    assert_eq!(swap.mint, Some(keys[BASE_MINT]));
+    assert_eq!(swap.user, Some(keys[USER]));
+    let liquidity = swap.liquidity.unwrap();
+    assert_eq!(liquidity.action, LiquidityAction::CreatePool);
+    assert_eq!(liquidity.pool, keys[POOL]);
+    assert_eq!(liquidity.base_mint, keys[BASE_MINT]);
+    assert_eq!(liquidity.quote_mint, keys[QUOTE_MINT]);
+    assert_eq!(liquidity.lp_mint, keys[LP_MINT]);
+    assert_eq!(liquidity.lp_amount, None);
+    assert_eq!(liquidity.base_amount, 200_000_000);
+    assert_eq!(liquidity.quote_amount, 80_000_000_000);
+}
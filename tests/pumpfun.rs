@@ -0,0 +1,69 @@
+mod common;
+
+use carbon_pumpfun_decoder::PROGRAM_ID as PUMPFUN;
+use common::{instruction_data, unique_keys};
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use teste::{decode_instruction, SwapMode};
+
+const WSOL: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+const BUY: [u8; 8] = [0x66, 0x06, 0x3d, 0x12, 0x01, 0xda, 0xeb, 0xea];
+const SELL: [u8; 8] = [0x33, 0xe6, 0x85, 0xa4, 0x01, 0x7f, 0x83, 0xad];
+
+// Account positions shared by `Buy` and `Sell`.
+const MINT: usize = 2;
+const ASSOCIATED_USER: usize = 5;
+const USER: usize = 6;
+const PROGRAM: usize = 11;
+
+/// A buy pays at most `max_sol_cost` for an exact amount of the token.
+#[test]
+fn buy_is_exact_out() {
+    let keys = unique_keys(12);
+
+    let swap = decode_instruction(
+        instruction_data(&BUY, &[5_000_000, 20_000_000]),
+        keys.clone(),
+        PUMPFUN,
+    )
+    .unwrap();
+
+    assert_eq!(swap.amm, Some(keys[PROGRAM]));
+    assert_eq!(swap.swap_mode, Some(SwapMode::ExactOut));
+    assert_eq!(swap.amount_out, Some(5_000_000));
+    assert_eq!(swap.max_amount_in, Some(20_000_000));
+    assert_eq!(swap.amount_in, None);
+    assert_eq!(swap.min_amount_out, None);
+    assert_eq!(swap.mint_token_in, Some(WSOL));
+    assert_eq!(swap.mint_token_out, Some(keys[MINT]));
+    assert_eq!(swap.mint_token_account_in, Some(keys[USER]));
+    assert_eq!(swap.mint_token_account_out, Some(keys[ASSOCIATED_USER]));
+    assert_eq!(swap.user, Some(keys[USER]));
+}
+
+/// A sell gives up an exact amount of the token for at least
+/// `min_sol_output`.
+#[test]
+fn sell_is_exact_in() {
+    let keys = unique_keys(12);
+
+    let swap = decode_instruction(
+        instruction_data(&SELL, &[5_000_000, 19_000_000]),
+        keys.clone(),
+        PUMPFUN,
+    )
+    .unwrap();
+
+    assert_eq!(swap.amm, Some(keys[PROGRAM]));
+    assert_eq!(swap.swap_mode, Some(SwapMode::ExactIn));
+    assert_eq!(swap.amount_in, Some(5_000_000));
+    assert_eq!(swap.min_amount_out, Some(19_000_000));
+    assert_eq!(swap.max_amount_in, None);
+    assert_eq!(swap.amount_out, None);
+    assert_eq!(swap.mint_token_in, Some(keys[MINT]));
+    assert_eq!(swap.mint_token_out, Some(WSOL));
+    assert_eq!(swap.mint_token_account_in, Some(keys[ASSOCIATED_USER]));
+    assert_eq!(swap.mint_token_account_out, Some(keys[USER]));
+    assert_eq!(swap.user, Some(keys[USER]));
+}
@@ -0,0 +1,241 @@
+mod common;
+
+use carbon_core::deserialize::CarbonDeserialize;
+use carbon_raydium_amm_v4_decoder::accounts::amm_info::{AmmInfo, AMM_INFO_SIZE};
+use carbon_raydium_amm_v4_decoder::PROGRAM_ID as RAYDIUM_AMM_V4;
+use common::{instruction, instruction_data, unique_keys, TransactionBuilder, TOKEN_PROGRAM};
+use serde_json::json;
+use solana_sdk::bs58;
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use teste::{
+    decode_instruction, decode_transaction_with_context, DecodeContext, PoolStateCache,
+    PoolStateProvider, SwapMode,
+};
+
+const SYSTEM_PROGRAM: Pubkey = pubkey!("11111111111111111111111111111111");
+
+const SWAP_BASE_IN: [u8; 1] = [9];
+const SWAP_BASE_OUT: [u8; 1] = [11];
+
+// Account positions of the swaps without `amm_target_orders`.
+const AMM: usize = 1;
+const SOURCE_TOKEN_ACCOUNT: usize = 14;
+const DESTINATION_TOKEN_ACCOUNT: usize = 15;
+const SOURCE_OWNER: usize = 16;
+
+/// Offsets of the vaults and mints in an `AmmInfo` account: they follow
+/// sixteen `u64`s, the fees and the output data.
+const TOKEN_COIN_OFFSET: usize = 336;
+const COIN_MINT_OFFSET: usize = 400;
+const PC_MINT_OFFSET: usize = 432;
+
+// Account indexes of the fixture transaction.
+const USER_SOURCE: u8 = 1;
+const USER_DESTINATION: u8 = 2;
+const COIN_VAULT: u8 = 6;
+
+struct Pool {
+    amm: Pubkey,
+    token_coin: Pubkey,
+    coin_mint: Pubkey,
+    pc_mint: Pubkey,
+}
+
+impl Pool {
+    fn new() -> Self {
+        Pool {
+            amm: Pubkey::new_unique(),
+            token_coin: Pubkey::new_unique(),
+            coin_mint: Pubkey::new_unique(),
+            pc_mint: Pubkey::new_unique(),
+        }
+    }
+
+    /// The pool's `AmmInfo` account data, zeroed but for the coin vault and
+    /// the mints.
+    fn account_data(&self) -> Vec<u8> {
+        let mut data = vec![0; AMM_INFO_SIZE];
+        for (offset, key) in [
+            (TOKEN_COIN_OFFSET, self.token_coin),
+            (COIN_MINT_OFFSET, self.coin_mint),
+            (PC_MINT_OFFSET, self.pc_mint),
+        ] {
+            data[offset..offset + 32].copy_from_slice(&key.to_bytes());
+        }
+        data
+    }
+
+    fn amm_info(&self) -> AmmInfo {
+        AmmInfo::deserialize(&self.account_data()).unwrap()
+    }
+
+    fn provider(&self) -> HashMap<Pubkey, AmmInfo> {
+        HashMap::from([(self.amm, self.amm_info())])
+    }
+}
+
+#[test]
+fn swap_base_in_is_exact_in() {
+    let keys = unique_keys(17);
+
+    let swap = decode_instruction(
+        instruction_data(&SWAP_BASE_IN, &[1_000, 400]),
+        keys.clone(),
+        RAYDIUM_AMM_V4,
+    )
+    .unwrap();
+
+    assert_eq!(swap.amm, Some(keys[AMM]));
+    assert_eq!(swap.swap_mode, Some(SwapMode::ExactIn));
+    assert_eq!(swap.amount_in, Some(1_000));
+    assert_eq!(swap.min_amount_out, Some(400));
+    assert_eq!(swap.max_amount_in, None);
+    assert_eq!(swap.amount_out, None);
+    assert_eq!(swap.mint_token_account_in, Some(keys[SOURCE_TOKEN_ACCOUNT]));
+    assert_eq!(
+        swap.mint_token_account_out,
+        Some(keys[DESTINATION_TOKEN_ACCOUNT])
+    );
+    assert_eq!(swap.user, Some(keys[SOURCE_OWNER]));
+}
+
+#[test]
+fn swap_base_out_is_exact_out() {
+    let keys = unique_keys(17);
+
+    let swap = decode_instruction(
+        instruction_data(&SWAP_BASE_OUT, &[1_100, 400]),
+        keys.clone(),
+        RAYDIUM_AMM_V4,
+    )
+    .unwrap();
+
+    assert_eq!(swap.amm, Some(keys[AMM]));
+    assert_eq!(swap.swap_mode, Some(SwapMode::ExactOut));
+    assert_eq!(swap.max_amount_in, Some(1_100));
+    assert_eq!(swap.amount_out, Some(400));
+    assert_eq!(swap.amount_in, None);
+    assert_eq!(swap.min_amount_out, None);
+    assert_eq!(swap.mint_token_account_in, Some(keys[SOURCE_TOKEN_ACCOUNT]));
+    assert_eq!(
+        swap.mint_token_account_out,
+        Some(keys[DESTINATION_TOKEN_ACCOUNT])
+    );
+    assert_eq!(swap.user, Some(keys[SOURCE_OWNER]));
+}
+
+/// A `SwapBaseIn` through `pool` from the user's account 1 into account 2.
+/// The instruction names neither mint.
+fn pool_swap(pool: &Pool) -> TransactionBuilder {
+    let user = Pubkey::new_unique();
+    let mut data = vec![9];
+    data.extend(1_000u64.to_le_bytes());
+    data.extend(400u64.to_le_bytes());
+    let mut accounts = vec![3];
+    accounts.extend([5; 13]);
+    accounts.extend([USER_SOURCE, USER_DESTINATION, 0]);
+
+    TransactionBuilder::new(vec![
+        user,
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        TOKEN_PROGRAM,
+        RAYDIUM_AMM_V4,
+        pool.amm,
+        pool.token_coin,
+    ])
+    .instruction(instruction(4, &accounts, &data, None))
+}
+
+/// The mint of the user's source account says which side was sold; the
+/// other mint only the pool state knows.
+#[test]
+fn direction_from_the_source_mint() {
+    let pool = Pool::new();
+    let owner = Pubkey::new_unique();
+    let transaction = pool_swap(&pool)
+        .token_balance(USER_SOURCE, pool.pc_mint, owner, Some(1_000), Some(0))
+        .build();
+    let pools = pool.provider();
+
+    let decoded = decode_transaction_with_context(
+        &transaction,
+        DecodeContext {
+            pool_state: Some(&pools),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let swap = &decoded.swaps[0].swap;
+    assert_eq!(swap.mint_token_in, Some(pool.pc_mint));
+    assert_eq!(swap.mint_token_out, Some(pool.coin_mint));
+}
+
+/// A source account without token balances, such as a temporary WSOL
+/// account, leaves the coin vault to tell: it grew, so coin was sold.
+#[test]
+fn direction_from_the_coin_vault() {
+    let pool = Pool::new();
+    let owner = Pubkey::new_unique();
+    let transaction = pool_swap(&pool)
+        .token_balance(USER_DESTINATION, pool.pc_mint, owner, Some(0), Some(450))
+        .token_balance(
+            COIN_VAULT,
+            pool.coin_mint,
+            pool.amm,
+            Some(9_000),
+            Some(10_000),
+        )
+        .build();
+    let pools = pool.provider();
+
+    let decoded = decode_transaction_with_context(
+        &transaction,
+        DecodeContext {
+            pool_state: Some(&pools),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let swap = &decoded.swaps[0].swap;
+    assert_eq!(swap.mint_token_in, Some(pool.coin_mint));
+    assert_eq!(swap.mint_token_out, Some(pool.pc_mint));
+}
+
+/// Snapshots hold whatever accounts were fetched; only AMM pools are kept.
+#[test]
+fn snapshot_keeps_only_amm_pools() {
+    let pool = Pool::new();
+    let other = Pubkey::new_unique();
+    let account = |owner: Pubkey, data: &[u8]| {
+        json!({
+            "lamports": 1_000_000,
+            "data": [bs58::encode(data).into_string(), "base58"],
+            "owner": owner.to_string(),
+            "executable": false,
+            "rentEpoch": 0,
+            "space": data.len(),
+        })
+    };
+    let snapshot = json!([
+        { "pubkey": pool.amm.to_string(), "account": account(RAYDIUM_AMM_V4, &pool.account_data()) },
+        { "pubkey": other.to_string(), "account": account(SYSTEM_PROGRAM, &[1, 2, 3]) },
+    ]);
+    let path = std::env::temp_dir().join(format!("raydium-pools-{}.json", pool.amm));
+    std::fs::write(&path, snapshot.to_string()).unwrap();
+
+    let cache = PoolStateCache::new();
+    let loaded = cache.load_snapshot(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.unwrap(), 1);
+    let amm_info = cache.amm_info(&pool.amm).unwrap();
+    assert_eq!(amm_info.token_coin, pool.token_coin);
+    assert_eq!(amm_info.coin_mint, pool.coin_mint);
+    assert_eq!(amm_info.pc_mint, pool.pc_mint);
+    assert_eq!(cache.amm_info(&other), None);
+}
@@ -0,0 +1,143 @@
+mod common;
+
+use carbon_raydium_launchpad_decoder::PROGRAM_ID as LAUNCHPAD;
+use common::{instruction_data, unique_keys};
+use teste::{decode_instruction, LifecycleStage, SwapMode, TokenMetadata};
+
+const SELL_EXACT_IN: [u8; 8] = [0x95, 0x27, 0xde, 0x9b, 0xd3, 0x7c, 0x98, 0x1a];
+const SELL_EXACT_OUT: [u8; 8] = [0x5f, 0xc8, 0x47, 0x22, 0x08, 0x09, 0x0b, 0xa6];
+const INITIALIZE: [u8; 8] = [0xaf, 0xaf, 0x6d, 0x1f, 0x0d, 0x98, 0x9b, 0xed];
+const MIGRATE_TO_AMM: [u8; 8] = [0xcf, 0x52, 0xc0, 0x91, 0xfe, 0xcf, 0x91, 0xdf];
+const MIGRATE_TO_CPSWAP: [u8; 8] = [0x88, 0x5c, 0xc8, 0x67, 0x1c, 0xda, 0x90, 0x8c];
+
+// Account positions of the sell instructions.
+const PAYER: usize = 0;
+const USER_BASE_TOKEN: usize = 5;
+const USER_QUOTE_TOKEN: usize = 6;
+const BASE_TOKEN_MINT: usize = 9;
+const QUOTE_TOKEN_MINT: usize = 10;
+
+fn borsh_string(data: &mut Vec<u8>, value: &str) {
+    data.extend((value.len() as u32).to_le_bytes());
+    data.extend(value.as_bytes());
+}
+
+/// A sell gives up base tokens for quote.
+#[test]
+fn sell_exact_in_trades_base_for_quote() {
+    let keys = unique_keys(15);
+
+    let swap = decode_instruction(
+        instruction_data(&SELL_EXACT_IN, &[1_000_000, 25_000, 0]),
+        keys.clone(),
+        LAUNCHPAD,
+    )
+    .unwrap();
+
+    assert_eq!(swap.amm, Some(LAUNCHPAD));
+    assert_eq!(swap.swap_mode, Some(SwapMode::ExactIn));
+    assert_eq!(swap.amount_in, Some(1_000_000));
+    assert_eq!(swap.min_amount_out, Some(25_000));
+    assert_eq!(swap.mint_token_in, Some(keys[BASE_TOKEN_MINT]));
+    assert_eq!(swap.mint_token_out, Some(keys[QUOTE_TOKEN_MINT]));
+    assert_eq!(swap.mint_token_account_in, Some(keys[USER_BASE_TOKEN]));
+    assert_eq!(swap.mint_token_account_out, Some(keys[USER_QUOTE_TOKEN]));
+    assert_eq!(swap.user, Some(keys[PAYER]));
+}
+
+#[test]
+fn sell_exact_out_trades_base_for_quote() {
+    let keys = unique_keys(15);
+
+    let swap = decode_instruction(
+        instruction_data(&SELL_EXACT_OUT, &[25_000, 1_000_000, 0]),
+        keys.clone(),
+        LAUNCHPAD,
+    )
+    .unwrap();
+
+    assert_eq!(swap.swap_mode, Some(SwapMode::ExactOut));
+    assert_eq!(swap.amount_out, Some(25_000));
+    assert_eq!(swap.max_amount_in, Some(1_000_000));
+    assert_eq!(swap.mint_token_in, Some(keys[BASE_TOKEN_MINT]));
+    assert_eq!(swap.mint_token_out, Some(keys[QUOTE_TOKEN_MINT]));
+    assert_eq!(swap.mint_token_account_in, Some(keys[USER_BASE_TOKEN]));
+    assert_eq!(swap.mint_token_account_out, Some(keys[USER_QUOTE_TOKEN]));
+}
+
+/// Launching a token reports its pool, mints and metadata.
+#[test]
+fn initialize_launches_the_token() {
+    let keys = unique_keys(18);
+    let mut initialize = INITIALIZE.to_vec();
+    initialize.push(6);
+    borsh_string(&mut initialize, "Example");
+    borsh_string(&mut initialize, "EXM");
+    borsh_string(&mut initialize, "https://example.com/token.json");
+    // A zeroed constant curve: three amounts and the migrate type.
+    initialize.push(0);
+    initialize.extend([0; 3 * 8 + 1]);
+    // No vesting.
+    initialize.extend([0; 3 * 8]);
+
+    let swap = decode_instruction(initialize, keys.clone(), LAUNCHPAD).unwrap();
+
+    assert_eq!(swap.mint_token_in, None);
+    assert_eq!(swap.mint_token_out, None);
+    assert_eq!(swap.amm, Some(LAUNCHPAD));
+    assert_eq!(swap.mint, Some(keys[6]));
+    assert_eq!(swap.user, Some(keys[1]));
+    let lifecycle = swap.lifecycle.unwrap();
+    assert_eq!(lifecycle.stage, LifecycleStage::Launched);
+    assert_eq!(lifecycle.pool, keys[5]);
+    assert_eq!(lifecycle.base_mint, keys[6]);
+    assert_eq!(lifecycle.quote_mint, keys[7]);
+    assert_eq!(lifecycle.destination_pool, None);
+    assert_eq!(
+        lifecycle.metadata,
+        Some(TokenMetadata {
+            name: "Example".to_string(),
+            symbol: "EXM".to_string(),
+            uri: "https://example.com/token.json".to_string(),
+            decimals: 6,
+        })
+    );
+}
+
+#[test]
+fn migrate_to_amm_names_the_amm_pool() {
+    let keys = unique_keys(32);
+    let mut migrate = instruction_data(&MIGRATE_TO_AMM, &[1, 1]);
+    migrate.push(0);
+
+    let swap = decode_instruction(migrate, keys.clone(), LAUNCHPAD).unwrap();
+
+    assert_eq!(swap.amm, Some(LAUNCHPAD));
+    assert_eq!(swap.mint, Some(keys[1]));
+    assert_eq!(swap.user, Some(keys[0]));
+    let lifecycle = swap.lifecycle.unwrap();
+    assert_eq!(lifecycle.stage, LifecycleStage::MigratedToAmm);
+    assert_eq!(lifecycle.pool, keys[23]);
+    assert_eq!(lifecycle.base_mint, keys[1]);
+    assert_eq!(lifecycle.quote_mint, keys[2]);
+    assert_eq!(lifecycle.destination_pool, Some(keys[13]));
+    assert_eq!(lifecycle.metadata, None);
+}
+
+#[test]
+fn migrate_to_cpswap_names_the_cpmm_pool() {
+    let keys = unique_keys(28);
+
+    let swap = decode_instruction(MIGRATE_TO_CPSWAP.to_vec(), keys.clone(), LAUNCHPAD).unwrap();
+
+    assert_eq!(swap.amm, Some(LAUNCHPAD));
+    assert_eq!(swap.mint, Some(keys[1]));
+    assert_eq!(swap.user, Some(keys[0]));
+    let lifecycle = swap.lifecycle.unwrap();
+    assert_eq!(lifecycle.stage, LifecycleStage::MigratedToCpswap);
+    assert_eq!(lifecycle.pool, keys[17]);
+    assert_eq!(lifecycle.base_mint, keys[1]);
+    assert_eq!(lifecycle.quote_mint, keys[2]);
+    assert_eq!(lifecycle.destination_pool, Some(keys[5]));
+    assert_eq!(lifecycle.metadata, None);
+}
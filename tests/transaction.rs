@@ -0,0 +1,434 @@
+mod common;
+
+use carbon_pumpfun_decoder::PROGRAM_ID as PUMPFUN;
+use common::{instruction, parse, token_transfer, TransactionBuilder, TOKEN_PROGRAM};
+use serde_json::{json, Value};
+use solana_sdk::instruction::CompiledInstruction;
+use solana_sdk::message::MessageHeader;
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use teste::{
+    decode_compiled_instruction, decode_transaction, decode_transaction_with_resolver, DecodeError,
+    MessageAccounts,
+};
+
+const SYSTEM_PROGRAM: Pubkey = pubkey!("11111111111111111111111111111111");
+
+const PUMPFUN_BUY: [u8; 8] = [0x66, 0x06, 0x3d, 0x12, 0x01, 0xda, 0xeb, 0xea];
+const PUMPFUN_TRADE_EVENT: [u8; 16] = [
+    0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d, 0xbd, 0xdb, 0x7f, 0xd3, 0x4e, 0xe6, 0x61, 0xee,
+];
+
+const FEE: u64 = 5_000;
+const SOL_SPENT: u64 = 500_000_000;
+const TOKENS_BOUGHT: u64 = 1_000_000;
+
+/// Accounts of a v0 transaction in which a trading bot buys a pump.fun token
+/// with native SOL. The global state, mint, fee accounts and event authority
+/// are loaded from one lookup table.
+struct PumpBuy {
+    user: Pubkey,
+    associated_user: Pubkey,
+    bonding_curve: Pubkey,
+    associated_bonding_curve: Pubkey,
+    router: Pubkey,
+    fee_recipient: Pubkey,
+    creator_vault: Pubkey,
+    global: Pubkey,
+    mint: Pubkey,
+    event_authority: Pubkey,
+    lookup_table: Pubkey,
+}
+
+impl PumpBuy {
+    fn new() -> Self {
+        PumpBuy {
+            user: Pubkey::new_unique(),
+            associated_user: Pubkey::new_unique(),
+            bonding_curve: Pubkey::new_unique(),
+            associated_bonding_curve: Pubkey::new_unique(),
+            router: Pubkey::new_unique(),
+            fee_recipient: Pubkey::new_unique(),
+            creator_vault: Pubkey::new_unique(),
+            global: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            event_authority: Pubkey::new_unique(),
+            lookup_table: Pubkey::new_unique(),
+        }
+    }
+
+    /// Static keys, in message order: the payer, three writable accounts and
+    /// four readonly programs.
+    fn static_keys(&self) -> Vec<Pubkey> {
+        vec![
+            self.user,
+            self.associated_user,
+            self.bonding_curve,
+            self.associated_bonding_curve,
+            self.router,
+            PUMPFUN,
+            SYSTEM_PROGRAM,
+            TOKEN_PROGRAM,
+        ]
+    }
+
+    /// Contents of the lookup table, in table order.
+    fn table(&self) -> Vec<Pubkey> {
+        vec![
+            self.global,
+            self.fee_recipient,
+            self.mint,
+            self.creator_vault,
+            self.event_authority,
+        ]
+    }
+
+    /// The runtime appends writable lookups (8, 9) before readonly ones
+    /// (10, 11, 12).
+    fn loaded_writable(&self) -> Vec<Pubkey> {
+        vec![self.fee_recipient, self.creator_vault]
+    }
+
+    fn loaded_readonly(&self) -> Vec<Pubkey> {
+        vec![self.global, self.mint, self.event_authority]
+    }
+
+    fn buy(&self, stack_height: Option<u32>) -> Value {
+        let mut data = PUMPFUN_BUY.to_vec();
+        data.extend(TOKENS_BOUGHT.to_le_bytes());
+        data.extend(600_000_000u64.to_le_bytes());
+        instruction(
+            5,
+            &[10, 8, 11, 2, 3, 1, 0, 6, 7, 9, 12, 5],
+            &data,
+            stack_height,
+        )
+    }
+
+    /// The `TradeEvent` pump.fun emits for the buy, as a CPI into itself.
+    fn trade_event(&self) -> Value {
+        let mut data = PUMPFUN_TRADE_EVENT.to_vec();
+        data.extend(self.mint.to_bytes());
+        data.extend(SOL_SPENT.to_le_bytes());
+        data.extend(TOKENS_BOUGHT.to_le_bytes());
+        data.push(1);
+        data.extend(self.user.to_bytes());
+        data.extend(0i64.to_le_bytes());
+        data.extend([0; 4 * 8]);
+        data.extend(self.fee_recipient.to_bytes());
+        data.extend([0; 2 * 8]);
+        data.extend(Pubkey::default().to_bytes());
+        data.extend([0; 2 * 8]);
+        instruction(5, &[12], &data, Some(3))
+    }
+
+    /// The router's outer instruction, which CPIs into pump.fun; the buy
+    /// pays the bonding curve in SOL, receives the tokens and logs a trade
+    /// event.
+    fn transaction(&self) -> Value {
+        let mut system_transfer = vec![2, 0, 0, 0];
+        system_transfer.extend(SOL_SPENT.to_le_bytes());
+
+        TransactionBuilder::new(self.static_keys())
+            .readonly_unsigned(4)
+            .instruction(instruction(4, &[0, 1, 2, 3, 5], &[], None))
+            .lookup(
+                self.lookup_table,
+                &[(1, self.fee_recipient), (3, self.creator_vault)],
+                &[(0, self.global), (2, self.mint), (4, self.event_authority)],
+            )
+            .inner(
+                0,
+                vec![
+                    self.buy(Some(2)),
+                    instruction(6, &[0, 2], &system_transfer, Some(3)),
+                    token_transfer(7, 3, 1, 2, TOKENS_BOUGHT, 3),
+                    self.trade_event(),
+                ],
+            )
+            .fee(FEE)
+            .lamports(0, 10_000_000_000, 10_000_000_000 - SOL_SPENT - FEE)
+            .lamports(2, 30_000_000_000, 30_000_000_000 + SOL_SPENT)
+            .token_balance(1, self.mint, self.user, None, Some(TOKENS_BOUGHT))
+            .json()
+    }
+}
+
+#[test]
+fn nested_cpis_get_their_stack_path() {
+    let fixture = PumpBuy::new();
+    let mut transaction = fixture.transaction();
+    let inner = transaction["meta"]["innerInstructions"][0]["instructions"]
+        .as_array_mut()
+        .unwrap();
+    // A buy one level deeper, next to the transfers and the trade event at
+    // height 3.
+    inner.push(fixture.buy(Some(3)));
+    // Back up to a direct CPI of the router.
+    inner.push(fixture.buy(Some(2)));
+    // Nodes recorded before stack heights count as direct CPIs.
+    inner.push(fixture.buy(None));
+
+    let decoded = decode_transaction(&parse(transaction)).unwrap();
+
+    let paths: Vec<Vec<usize>> = decoded.swaps.iter().map(|swap| swap.path.clone()).collect();
+    assert_eq!(
+        paths,
+        vec![vec![0, 0], vec![0, 0, 3], vec![0, 1], vec![0, 2]]
+    );
+    // The trade events are pump.fun instructions too, but not swaps.
+    assert!(decoded.failures.is_empty());
+}
+
+/// Asserts that the buy's accounts were read from the right places: the
+/// static keys, then the writable lookups, then the readonly ones.
+fn assert_buy_accounts(fixture: &PumpBuy, decoded: &teste::TransactionSwaps) {
+    let buy = &decoded.swaps[0].swap;
+    assert_eq!(buy.mint_token_out, Some(fixture.mint));
+    assert_eq!(buy.mint_token_account_out, Some(fixture.associated_user));
+    assert_eq!(buy.user, Some(fixture.user));
+}
+
+#[test]
+fn loaded_addresses_follow_static_keys_writable_first() {
+    let fixture = PumpBuy::new();
+
+    let decoded = decode_transaction(&parse(fixture.transaction())).unwrap();
+
+    assert_buy_accounts(&fixture, &decoded);
+}
+
+#[test]
+fn resolver_fills_in_missing_loaded_addresses() {
+    let fixture = PumpBuy::new();
+    let mut transaction = fixture.transaction();
+    transaction["meta"]
+        .as_object_mut()
+        .unwrap()
+        .remove("loadedAddresses");
+    let resolver = HashMap::from([(fixture.lookup_table, fixture.table())]);
+
+    let decoded = decode_transaction_with_resolver(&parse(transaction), Some(&resolver)).unwrap();
+
+    assert_buy_accounts(&fixture, &decoded);
+}
+
+#[test]
+fn unresolved_lookup_table_is_an_error() {
+    let fixture = PumpBuy::new();
+    let mut transaction = fixture.transaction();
+    transaction["meta"]
+        .as_object_mut()
+        .unwrap()
+        .remove("loadedAddresses");
+    let transaction = parse(transaction);
+
+    assert_eq!(
+        decode_transaction(&transaction).unwrap_err(),
+        DecodeError::MissingLookupTable(fixture.lookup_table)
+    );
+    let other_tables = HashMap::from([(Pubkey::new_unique(), fixture.table())]);
+    assert_eq!(
+        decode_transaction_with_resolver(&transaction, Some(&other_tables)).unwrap_err(),
+        DecodeError::MissingLookupTable(fixture.lookup_table)
+    );
+}
+
+/// Positions of the buy's transfers among the router's CPIs.
+const SYSTEM_TRANSFER_CPI: usize = 1;
+const TOKEN_TRANSFER_CPI: usize = 2;
+
+/// Drops CPI `index` of the router, so the balances are all that is left to
+/// read the buy's amounts from.
+fn remove_cpi(transaction: &mut Value, index: usize) {
+    transaction["meta"]["innerInstructions"][0]["instructions"]
+        .as_array_mut()
+        .unwrap()
+        .remove(index);
+}
+
+#[test]
+fn buy_amounts_from_its_transfers() {
+    let fixture = PumpBuy::new();
+
+    let decoded = decode_transaction(&parse(fixture.transaction())).unwrap();
+
+    let buy = &decoded.swaps[0].swap;
+    assert_eq!(buy.mint_token_account_in, Some(fixture.user));
+    assert_eq!(buy.realized_in, Some(SOL_SPENT));
+    assert_eq!(buy.realized_out, Some(TOKENS_BOUGHT));
+}
+
+/// The payer's lamports drop by the SOL spent plus the transaction fee; only
+/// the former is swap input.
+#[test]
+fn native_sol_buy_adds_the_fee_back() {
+    let fixture = PumpBuy::new();
+    let mut transaction = fixture.transaction();
+    remove_cpi(&mut transaction, SYSTEM_TRANSFER_CPI);
+
+    let decoded = decode_transaction(&parse(transaction)).unwrap();
+
+    assert_eq!(decoded.swaps[0].swap.realized_in, Some(SOL_SPENT));
+}
+
+/// Token accounts missing from the pre balances were created by the
+/// transaction and start from zero.
+#[test]
+fn token_account_created_in_the_transaction() {
+    let fixture = PumpBuy::new();
+    let mut transaction = fixture.transaction();
+    remove_cpi(&mut transaction, TOKEN_TRANSFER_CPI);
+    transaction["meta"]["postTokenBalances"][0]["uiTokenAmount"]["amount"] = "250".into();
+
+    let decoded = decode_transaction(&parse(transaction)).unwrap();
+
+    assert_eq!(decoded.swaps[0].swap.realized_out, Some(250));
+}
+
+/// The payer also funds the rent of the token account the buy is paid out
+/// to, which is not swap input whether or not the transfers are recorded.
+#[test]
+fn associated_token_account_rent_is_not_input() {
+    const RENT: u64 = 2_039_280;
+    let fixture = PumpBuy::new();
+    let mut transaction = fixture.transaction();
+    let meta = &mut transaction["meta"];
+    meta["postBalances"][0] = (10_000_000_000 - SOL_SPENT - FEE - RENT).into();
+    meta["postBalances"][1] = RENT.into();
+
+    let decoded = decode_transaction(&parse(transaction.clone())).unwrap();
+    assert_eq!(decoded.swaps[0].swap.realized_in, Some(SOL_SPENT));
+
+    remove_cpi(&mut transaction, SYSTEM_TRANSFER_CPI);
+    let decoded = decode_transaction(&parse(transaction)).unwrap();
+    assert_eq!(decoded.swaps[0].swap.realized_in, Some(SOL_SPENT));
+}
+
+/// A Raydium swap out of a temporary WSOL account that is created and closed
+/// within the transaction, so it has no token balances and no lamports left.
+fn closed_wsol_swap() -> TransactionBuilder {
+    let user = Pubkey::new_unique();
+    let usdc = Pubkey::new_unique();
+    let mut data = vec![9];
+    data.extend(SOL_SPENT.to_le_bytes());
+    data.extend(TOKENS_BOUGHT.to_le_bytes());
+    let mut accounts = vec![3];
+    accounts.extend([5; 13]);
+    accounts.extend([1, 2, 0]);
+
+    TransactionBuilder::new(vec![
+        user,
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        TOKEN_PROGRAM,
+        carbon_raydium_amm_v4_decoder::PROGRAM_ID,
+        Pubkey::new_unique(),
+    ])
+    .readonly_unsigned(2)
+    .instruction(instruction(4, &accounts, &data, None))
+    .inner(
+        0,
+        vec![
+            token_transfer(3, 1, 5, 0, SOL_SPENT, 2),
+            token_transfer(3, 5, 2, 5, TOKENS_BOUGHT, 2),
+        ],
+    )
+    .fee(FEE)
+    .lamports(0, 10_000_000_000, 10_000_000_000 - SOL_SPENT - FEE)
+    .token_balance(2, usdc, user, Some(0), Some(TOKENS_BOUGHT))
+}
+
+#[test]
+fn closed_wsol_account_input_from_its_transfer() {
+    let decoded = decode_transaction(&closed_wsol_swap().build()).unwrap();
+
+    let swap = &decoded.swaps[0].swap;
+    assert_eq!(swap.realized_in, Some(SOL_SPENT));
+    assert_eq!(swap.realized_out, Some(TOKENS_BOUGHT));
+}
+
+/// Without the transfers nothing is known about an account that ends the
+/// transaction as it started.
+#[test]
+fn closed_wsol_account_without_inner_instructions() {
+    let transaction = closed_wsol_swap().without_inner_instructions().build();
+
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    let swap = &decoded.swaps[0].swap;
+    assert_eq!(swap.realized_in, None);
+    assert_eq!(swap.realized_out, Some(TOKENS_BOUGHT));
+}
+
+/// A swap that failed on chain moved nothing, so none of its instructions
+/// are reported.
+#[test]
+fn failed_transaction_has_no_swaps() {
+    let transaction = closed_wsol_swap()
+        .err(json!({ "InstructionError": [0, { "Custom": 30 }] }))
+        .build();
+
+    let decoded = decode_transaction(&transaction).unwrap();
+
+    assert!(decoded.failed);
+    assert!(decoded.swaps.is_empty());
+    assert!(decoded.failures.is_empty());
+}
+
+/// Realized amounts need the meta; without it only the instruction's own
+/// amounts are known.
+#[test]
+fn no_realized_amounts_without_meta() {
+    let fixture = PumpBuy::new();
+    let mut transaction = fixture.transaction();
+    // Inline the lookups into the static keys, since the meta that loaded
+    // them is gone.
+    let mut keys: Vec<Pubkey> = fixture.static_keys();
+    keys.extend(fixture.loaded_writable());
+    keys.extend(fixture.loaded_readonly());
+    let message = &mut transaction["transaction"]["message"];
+    message["accountKeys"] = keys.iter().map(Pubkey::to_string).collect();
+    message
+        .as_object_mut()
+        .unwrap()
+        .remove("addressTableLookups");
+    message["instructions"] = json!([fixture.buy(None)]);
+    transaction["meta"] = Value::Null;
+
+    let decoded = decode_transaction(&parse(transaction)).unwrap();
+
+    let buy = &decoded.swaps[0].swap;
+    assert_eq!(buy.amount_out, Some(TOKENS_BOUGHT));
+    assert_eq!(buy.realized_in, None);
+    assert_eq!(buy.realized_out, None);
+}
+
+/// A header that claims more readonly accounts than the message has must
+/// not panic the decoder.
+#[test]
+fn malformed_header_does_not_panic() {
+    let user = Pubkey::new_unique();
+    let accounts = MessageAccounts {
+        header: MessageHeader {
+            num_required_signatures: 1,
+            num_readonly_signed_accounts: 3,
+            num_readonly_unsigned_accounts: 9,
+        },
+        static_keys: vec![user, PUMPFUN],
+        ..Default::default()
+    };
+    let mut data = PUMPFUN_BUY.to_vec();
+    data.extend(TOKENS_BOUGHT.to_le_bytes());
+    data.extend(600_000_000u64.to_le_bytes());
+    let instruction = CompiledInstruction {
+        program_id_index: 1,
+        accounts: vec![0, 1],
+        data,
+    };
+
+    assert!(!accounts.is_writable(0));
+    assert!(!accounts.is_writable(1));
+    assert!(decode_compiled_instruction(&instruction, &accounts).is_err());
+}